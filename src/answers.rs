@@ -0,0 +1,528 @@
+//! Known answers and a harness that checks the registered solutions against
+//! them.
+//!
+//! The answers live in `answers.txt` at the crate root. Each line reads
+//! `day part input answer`, where `input` is the path of the puzzle input
+//! relative to the crate root; `#` starts a comment.
+//!
+//! An answer may be stored as `sha256:SALT:DIGEST` instead, where DIGEST is
+//! the hex SHA-256 of `SALT:ANSWER`, so the file can be published without
+//! giving the answers away. `my-project hash-answers` hashes every answer in
+//! the clear and keeps the plain copies in `answers.local.txt`, which stays
+//! out of git. The answers are small integers, so a digest can be reversed
+//! by trying them in turn from the salt: hashing keeps the answers from being
+//! read in passing, not from someone set on finding them.
+//!
+//! The puzzle text at the top of a `dayN.rs` may record the accepted answers
+//! too, as "Your puzzle answer was N.", which makes a second source to check
+//! the solvers and `answers.txt` against. The committed headers leave them
+//! out, so they do not give away what `answers.txt` hashes.
+
+use std::fmt::{self, Write};
+
+use nom::error::ErrorKind;
+use sha2::{Digest, Sha256};
+
+use crate::bytes;
+use crate::error::{with_input, PuzzleError};
+use crate::report::{self, Align};
+use crate::solution::{self, default_input, Answer, Params, Part, SOLUTIONS};
+use crate::vault;
+use crate::worker::{self, Limits};
+
+/// Where the answers are kept, relative to the crate root.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Where the plain copies of hashed answers are kept, relative to the crate
+/// root.
+pub const LOCAL_ANSWERS_FILE: &str = "answers.local.txt";
+
+/// An expected answer, in the clear or hashed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Plain(Answer),
+    Hashed { salt: String, digest: [u8; 32] },
+}
+
+impl Expected {
+    pub fn hash(answer: Answer, salt: &str) -> Expected {
+        Expected::Hashed {
+            salt: salt.to_string(),
+            digest: digest(answer, salt),
+        }
+    }
+
+    pub fn matches(&self, answer: Answer) -> bool {
+        match self {
+            Expected::Plain(expected) => *expected == answer,
+            Expected::Hashed { salt, digest: d } => digest(answer, salt) == *d,
+        }
+    }
+
+    /// Parses the answer `field`, a subslice of `text`.
+    fn parse(text: &str, field: &str) -> Result<Expected, PuzzleError> {
+        let Some(hashed) = field.strip_prefix("sha256:") else {
+            return field
+                .parse()
+                .map(|answer| Expected::Plain(Answer(answer)))
+                .map_err(|_| PuzzleError::parse(text, field, ErrorKind::Digit));
+        };
+        let (salt, hex) = hashed
+            .split_once(':')
+            .ok_or_else(|| PuzzleError::parse(text, hashed, ErrorKind::Char))?;
+        Ok(Expected::Hashed {
+            salt: salt.to_string(),
+            digest: bytes::unhex_exact(text, hex)?,
+        })
+    }
+}
+
+fn digest(answer: Answer, salt: &str) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(salt)
+        .chain_update(":")
+        .chain_update(answer.to_string())
+        .finalize()
+        .into()
+}
+
+/// As stored in the answers file.
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Plain(answer) => write!(f, "{answer}"),
+            Expected::Hashed { salt, digest } => {
+                write!(f, "sha256:{salt}:{}", bytes::hex(digest))
+            }
+        }
+    }
+}
+
+/// The expected answer to one part for one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Known {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Expected,
+}
+
+impl fmt::Display for Known {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.day, self.part, self.input, self.answer
+        )
+    }
+}
+
+pub fn parse(text: &str) -> Result<Vec<Known>, PuzzleError> {
+    let mut known = Vec::new();
+    for line in text.lines() {
+        let content = line.split('#').next().unwrap_or_default();
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let [day, part, input, answer] = fields[..] else {
+            return Err(PuzzleError::parse(text, line, ErrorKind::Count));
+        };
+        let day = day
+            .parse()
+            .map_err(|_| PuzzleError::parse(text, day, ErrorKind::Digit))?;
+        let part = part
+            .parse()
+            .ok()
+            .and_then(Part::from_number)
+            .ok_or_else(|| PuzzleError::parse(text, part, ErrorKind::OneOf))?;
+        known.push(Known {
+            day,
+            part,
+            input: input.to_string(),
+            answer: Expected::parse(text, answer)?,
+        });
+    }
+    Ok(known)
+}
+
+/// Renders `known` as an answers file.
+pub fn render(known: &[Known]) -> String {
+    let mut out = String::from("# day part input answer\n");
+    for k in known {
+        let _ = writeln!(out, "{k}");
+    }
+    out
+}
+
+/// A random salt, which no other hash is likely to share.
+pub fn fresh_salt() -> Result<String, PuzzleError> {
+    Ok(bytes::hex(&bytes::random::<8>()?))
+}
+
+/// Rewrites the answers file `text` with every plain answer hashed under a
+/// salt from `salt`, keeping comments and layout. Also returns the answers
+/// that were in the clear.
+pub fn hash_all(
+    text: &str,
+    mut salt: impl FnMut(&Known) -> Result<String, PuzzleError>,
+) -> Result<(String, Vec<Known>), PuzzleError> {
+    // Checked as a whole first, so errors point into the file.
+    parse(text)?;
+    let mut out = String::new();
+    let mut plain = Vec::new();
+    for line in text.lines() {
+        match parse(line)?.first() {
+            Some(
+                known @ Known {
+                    answer: Expected::Plain(answer),
+                    ..
+                },
+            ) => {
+                let hashed = Known {
+                    answer: Expected::hash(*answer, &salt(known)?),
+                    ..known.clone()
+                };
+                let comment = line.find('#').map_or("", |i| &line[i..]);
+                let _ = writeln!(out, "{}", format!("{hashed} {comment}").trim_end());
+                plain.push(known.clone());
+            }
+            _ => {
+                let _ = writeln!(out, "{line}");
+            }
+        }
+    }
+    Ok((out, plain))
+}
+
+/// Replaces the hashed answers in `known` with the plain ones in `local` that
+/// match them.
+pub fn reveal(known: &mut [Known], local: &[Known]) {
+    for k in known {
+        let plain = local.iter().find_map(|l| match l.answer {
+            Expected::Plain(answer)
+                if (l.day, l.part, &l.input) == (k.day, k.part, &k.input)
+                    && k.answer.matches(answer) =>
+            {
+                Some(answer)
+            }
+            _ => None,
+        });
+        if let Some(answer) = plain {
+            k.answer = Expected::Plain(answer);
+        }
+    }
+}
+
+/// Where the source of `day`, header and all, is kept.
+pub fn source_file(day: u8) -> String {
+    format!("src/day{day}.rs")
+}
+
+/// The answers recorded in the puzzle text heading a day's `source`, for the
+/// day's default input.
+pub fn from_header(day: u8, source: &str) -> Vec<Known> {
+    let Some(header) = source.strip_prefix("/*") else {
+        return Vec::new();
+    };
+    let header = header.split("*/").next().unwrap_or_default();
+    let mut part = Part::One;
+    let mut known = Vec::new();
+    for line in header.lines().map(str::trim) {
+        if line == "--- Part Two ---" {
+            part = Part::Two;
+        }
+        let answer = line
+            .strip_prefix("Your puzzle answer was ")
+            .and_then(|rest| rest.strip_suffix('.'))
+            .and_then(|answer| answer.parse().ok());
+        if let Some(answer) = answer {
+            known.push(Known {
+                day,
+                part,
+                input: default_input(day),
+                answer: Expected::Plain(Answer(answer)),
+            });
+        }
+    }
+    known
+}
+
+/// Reads the answers recorded in the header of every registered day.
+pub fn from_headers() -> Result<Vec<Known>, PuzzleError> {
+    let mut known = Vec::new();
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        known.extend(with_input(&source_file(day), |source| {
+            Ok(from_header(day, source))
+        })?);
+    }
+    Ok(known)
+}
+
+/// The answers for the same day, part and input that `ours` and `theirs`
+/// disagree on. Two hashed answers cannot be compared, so never disagree.
+pub fn drift<'a>(ours: &'a [Known], theirs: &'a [Known]) -> Vec<(&'a Known, &'a Known)> {
+    ours.iter()
+        .flat_map(|a| {
+            theirs
+                .iter()
+                .filter(move |b| {
+                    (a.day, a.part, &a.input) == (b.day, b.part, &b.input)
+                        && match (&a.answer, &b.answer) {
+                            (Expected::Plain(answer), other) | (other, Expected::Plain(answer)) => {
+                                !other.matches(*answer)
+                            }
+                            _ => false,
+                        }
+                })
+                .map(move |b| (a, b))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// The solver produced a different answer.
+    Wrong(Answer),
+    /// The solver, or finding it, failed.
+    Failed(String),
+    /// The input is not checked out, or is sealed and there is no key, so
+    /// there was nothing to solve.
+    Skipped,
+}
+
+/// The outcome of solving for one known answer.
+#[derive(Debug)]
+pub struct Check {
+    pub known: Known,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    /// Whether the solver reproduced the answer; a skipped check did not.
+    pub fn passed(&self) -> bool {
+        self.outcome == Outcome::Correct
+    }
+
+    pub fn skipped(&self) -> bool {
+        self.outcome == Outcome::Skipped
+    }
+}
+
+/// Solves every known answer, each on a worker within `limits`, carrying on
+/// past the ones that do not match or do not finish.
+pub fn verify(known: &[Known], limits: &Limits) -> Vec<Check> {
+    known
+        .iter()
+        .map(|known| Check {
+            known: known.clone(),
+            outcome: check(known, limits),
+        })
+        .collect()
+}
+
+fn check(known: &Known, limits: &Limits) -> Outcome {
+    let Known {
+        day, part, input, ..
+    } = known;
+    let Some(solution) = solution::find(*day) else {
+        return Outcome::Failed(format!("day {day} is not implemented"));
+    };
+    if !solution.parts().contains(part) {
+        return Outcome::Failed(format!("day {day} part {part} is not implemented"));
+    }
+    if !vault::available(input) {
+        return Outcome::Skipped;
+    }
+    match worker::solve_file(solution, *part, input, &Params::default(), limits) {
+        Ok(answer) if known.answer.matches(answer) => Outcome::Correct,
+        Ok(answer) => Outcome::Wrong(answer),
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+/// Lays the checks out as a table, one row each.
+pub fn table(checks: &[Check]) -> String {
+    let rows: Vec<[String; 5]> = checks
+        .iter()
+        .map(|Check { known, outcome }| {
+            let result = match outcome {
+                Outcome::Correct => "ok".to_string(),
+                Outcome::Wrong(answer) => format!("WRONG, got {answer}"),
+                Outcome::Failed(reason) => format!("FAILED, {reason}"),
+                Outcome::Skipped => "skipped, no input".to_string(),
+            };
+            let expected = match &known.answer {
+                Expected::Plain(answer) => answer.to_string(),
+                Expected::Hashed { .. } => "(hashed)".to_string(),
+            };
+            [
+                known.day.to_string(),
+                known.part.to_string(),
+                known.input.clone(),
+                expected,
+                result,
+            ]
+        })
+        .collect();
+    report::table(
+        ["day", "part", "input", "expected", "result"],
+        [
+            Align::Right,
+            Align::Right,
+            Align::Left,
+            Align::Right,
+            Align::Left,
+        ],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expected, Known, Outcome};
+    use crate::solution::{Answer, Part, SOLUTIONS};
+    use crate::worker::Limits;
+
+    #[test]
+    fn known_answers() {
+        let known = super::parse(include_str!("../answers.txt")).unwrap();
+        for solution in SOLUTIONS {
+            for &part in solution.parts() {
+                assert!(
+                    known
+                        .iter()
+                        .any(|k| (k.day, k.part) == (solution.day(), part)),
+                    "no known answer for day {} part {part}",
+                    solution.day()
+                );
+            }
+        }
+        // The inputs are private, so a checkout without them or their key
+        // skips rather than fails.
+        let checks = super::verify(&known, &Limits::default());
+        assert!(
+            checks.iter().all(|c| c.passed() || c.skipped()),
+            "\n{}",
+            super::table(&checks)
+        );
+    }
+
+    #[test]
+    fn committed_answers_are_hashed() {
+        let known = super::parse(include_str!("../answers.txt")).unwrap();
+        let plain: Vec<_> = known
+            .iter()
+            .filter(|k| matches!(k.answer, Expected::Plain(_)))
+            .map(|k| format!("day {} part {}", k.day, k.part))
+            .collect();
+        assert!(
+            plain.is_empty(),
+            "answers.txt has answers in the clear, for {}; run `my-project hash-answers`",
+            plain.join(", ")
+        );
+    }
+
+    #[test]
+    fn headers_record_no_answers() {
+        let recorded = super::from_headers().unwrap();
+        let days: Vec<_> = recorded
+            .iter()
+            .map(|k| format!("day {} part {}", k.day, k.part))
+            .collect();
+        assert!(
+            days.is_empty(),
+            "the puzzle headers give away the answers for {}",
+            days.join(", ")
+        );
+    }
+
+    #[test]
+    fn from_header() {
+        let source = "/*\n--- Day 4: Scratchcards ---\nYour puzzle answer was 13.\n\n\
+                      --- Part Two ---\nYour puzzle answer was 30.\n*/\n\
+                      // Your puzzle answer was 1.\n";
+        let known = super::from_header(4, source);
+        let answers: Vec<_> = known.iter().map(|k| (k.part, &k.answer)).collect();
+        assert_eq!(
+            answers,
+            [
+                (Part::One, &Expected::Plain(Answer(13))),
+                (Part::Two, &Expected::Plain(Answer(30)))
+            ]
+        );
+        assert_eq!(known[0].input, "src/day4_input.txt");
+        assert_eq!(super::from_header(4, "fn main() {}"), []);
+    }
+
+    #[test]
+    fn parse() {
+        let known = super::parse("# comment\n\n8 2 src/day8_input.txt 42 # fake\n").unwrap();
+        assert_eq!(
+            known,
+            [Known {
+                day: 8,
+                part: Part::Two,
+                input: "src/day8_input.txt".to_string(),
+                answer: Expected::Plain(Answer(42)),
+            }]
+        );
+        assert_eq!(
+            super::parse("1 1 a 2\n1 3 a 2").unwrap_err().to_string(),
+            "<input>:2:3: unexpected input (OneOf)"
+        );
+        assert!(super::parse("1 1 a").is_err());
+    }
+
+    #[test]
+    fn hashed() {
+        let text = "# day part input answer\n6 1 a.txt 288 # example\n6 2 a.txt sha256:00:ab\n";
+        assert_eq!(
+            super::parse(text).unwrap_err().to_string(),
+            "<input>:3:21: unexpected input (Length followed by value)"
+        );
+        let text = "# day part input answer\n6 1 a.txt 288 # example\n";
+        let (hashed, plain) = super::hash_all(text, |k| Ok(format!("salt{}", k.part))).unwrap();
+        assert_eq!(
+            hashed,
+            "# day part input answer\n6 1 a.txt sha256:salt1:\
+             9c25d4d88df0aab3f380128c968fcd44d309faaa26bd70820f9cb10aa8492b72 # example\n"
+        );
+        assert_eq!(plain, super::parse(text).unwrap());
+
+        let mut known = super::parse(&hashed).unwrap();
+        assert!(known[0].answer.matches(Answer(288)));
+        assert!(!known[0].answer.matches(Answer(289)));
+        assert_eq!(super::drift(&known, &plain), []);
+        let checks = super::verify(&known, &Limits::default());
+        assert!(super::table(&checks).contains("(hashed)"));
+        super::reveal(&mut known, &plain);
+        assert_eq!(known, plain);
+    }
+
+    #[test]
+    fn mismatches_do_not_stop_the_run() {
+        // The puzzle inputs may be sealed, so use day 6's example instead.
+        let input = std::env::temp_dir().join(format!("my-project-{}-day6", std::process::id()));
+        std::fs::write(&input, "Time:      7  15   30\nDistance:  9  40  200\n").unwrap();
+        let known = |day, answer| Known {
+            day,
+            part: Part::One,
+            input: input.display().to_string(),
+            answer: Expected::Plain(Answer(answer)),
+        };
+        let checks = super::verify(
+            &[known(13, 0), known(6, 0), known(6, 288)],
+            &Limits::default(),
+        );
+        std::fs::remove_file(&input).unwrap();
+        assert!(matches!(checks[0].outcome, Outcome::Failed(_)));
+        assert!(matches!(checks[1].outcome, Outcome::Wrong(Answer(288))));
+        assert_eq!(checks[2].outcome, Outcome::Correct);
+        let table = super::table(&checks);
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.contains("WRONG, got 288"));
+    }
+}
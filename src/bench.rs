@@ -0,0 +1,373 @@
+//! Timing the registered solutions, parse and solve separately, and comparing
+//! the timings against a saved baseline.
+//!
+//! A baseline file has one line per day and part reading
+//! `day part parse_ns solve_ns`, the median nanoseconds each phase took;
+//! `#` starts a comment. `my-project bench --save PATH` writes one.
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use nom::error::ErrorKind;
+
+use crate::error::PuzzleError;
+use crate::report::{self, json_string, Align};
+use crate::solution::{Params, Part, Solution};
+
+/// The spread of a phase's timings over several runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// How long one part of one day took to parse and to solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    pub part: Part,
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// A part that could not be timed, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failed {
+    pub day: u8,
+    pub part: Part,
+    pub reason: String,
+}
+
+/// Parses `input` and solves `part` of it `runs` times, timing each phase.
+pub fn time(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+    runs: usize,
+) -> Result<Timing, PuzzleError> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let parsed = solution.parse(input)?;
+        let parsed_at = Instant::now();
+        parsed.solve(part, params)?;
+        parse.push(parsed_at - start);
+        solve.push(parsed_at.elapsed());
+    }
+    Ok(Timing {
+        day: solution.day(),
+        part,
+        runs: parse.len(),
+        parse: Stats::from_samples(&mut parse),
+        solve: Stats::from_samples(&mut solve),
+    })
+}
+
+/// One line per phase, with the spread of its timings, and one per part
+/// that failed.
+pub fn table(timings: &[Timing], failed: &[Failed]) -> String {
+    let mut rows: Vec<(u8, Part, [String; 6])> = timings
+        .iter()
+        .flat_map(|timing| {
+            [("parse", timing.parse), ("solve", timing.solve)].map(|(phase, stats)| {
+                let row = [
+                    timing.day.to_string(),
+                    timing.part.to_string(),
+                    phase.to_string(),
+                    format!("{:.1?}", stats.min),
+                    format!("{:.1?}", stats.median),
+                    format!("{:.1?}", stats.max),
+                ];
+                (timing.day, timing.part, row)
+            })
+        })
+        .collect();
+    rows.extend(failed.iter().map(|f| {
+        let row = [
+            f.day.to_string(),
+            f.part.to_string(),
+            "FAILED".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+        ];
+        (f.day, f.part, row)
+    }));
+    rows.sort_by_key(|&(day, part, _)| (day, part));
+    let rows: Vec<[String; 6]> = rows.into_iter().map(|(_, _, row)| row).collect();
+    report::table(
+        ["day", "part", "phase", "min", "median", "max"],
+        [
+            Align::Right,
+            Align::Right,
+            Align::Left,
+            Align::Right,
+            Align::Right,
+            Align::Right,
+        ],
+        &rows,
+    )
+}
+
+/// The timings as a JSON array, in nanoseconds, with an `error` in place of
+/// the phases for each part that failed.
+pub fn json(timings: &[Timing], failed: &[Failed]) -> String {
+    let stats = |s: Stats| {
+        format!(
+            "{{\"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.max.as_nanos()
+        )
+    };
+    let mut entries: Vec<(u8, Part, String)> = timings
+        .iter()
+        .map(|t| {
+            let entry = format!(
+                "  {{\"day\": {}, \"part\": {}, \"runs\": {}, \"parse\": {}, \"solve\": {}}}",
+                t.day,
+                t.part,
+                t.runs,
+                stats(t.parse),
+                stats(t.solve)
+            );
+            (t.day, t.part, entry)
+        })
+        .collect();
+    entries.extend(failed.iter().map(|f| {
+        let entry = format!(
+            "  {{\"day\": {}, \"part\": {}, \"error\": {}}}",
+            f.day,
+            f.part,
+            json_string(&f.reason)
+        );
+        (f.day, f.part, entry)
+    }));
+    entries.sort_by_key(|&(day, part, _)| (day, part));
+    let entries: Vec<String> = entries.into_iter().map(|(_, _, entry)| entry).collect();
+    report::json_array(&entries)
+}
+
+/// The median timings of one part, as saved in a baseline file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Baseline {
+    pub day: u8,
+    pub part: Part,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Renders the medians of `timings` as a baseline file.
+pub fn save(timings: &[Timing]) -> String {
+    let mut out = String::from("# day part parse_ns solve_ns\n");
+    for timing in timings {
+        let _ = writeln!(
+            out,
+            "{} {} {} {}",
+            timing.day,
+            timing.part,
+            timing.parse.median.as_nanos(),
+            timing.solve.median.as_nanos()
+        );
+    }
+    out
+}
+
+pub fn parse_baseline(text: &str) -> Result<Vec<Baseline>, PuzzleError> {
+    let mut baseline = Vec::new();
+    for line in text.lines() {
+        let content = line.split('#').next().unwrap_or_default();
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let [day, part, parse, solve] = fields[..] else {
+            return Err(PuzzleError::parse(text, line, ErrorKind::Count));
+        };
+        let day = day
+            .parse()
+            .map_err(|_| PuzzleError::parse(text, day, ErrorKind::Digit))?;
+        let part = part
+            .parse()
+            .ok()
+            .and_then(Part::from_number)
+            .ok_or_else(|| PuzzleError::parse(text, part, ErrorKind::OneOf))?;
+        let nanos = |field: &str| {
+            field
+                .parse()
+                .map(Duration::from_nanos)
+                .map_err(|_| PuzzleError::parse(text, field, ErrorKind::Digit))
+        };
+        baseline.push(Baseline {
+            day,
+            part,
+            parse: nanos(parse)?,
+            solve: nanos(solve)?,
+        });
+    }
+    Ok(baseline)
+}
+
+/// A phase whose median got slower than its baseline allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u8,
+    pub part: Part,
+    pub phase: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+/// The phases of `timings` whose medians are more than `threshold` percent
+/// slower than in `baseline`. Parts missing from the baseline are not
+/// compared.
+pub fn regressions(timings: &[Timing], baseline: &[Baseline], threshold: f64) -> Vec<Regression> {
+    let limit = 1.0 + threshold / 100.0;
+    let mut found = Vec::new();
+    for timing in timings {
+        let Some(base) = baseline
+            .iter()
+            .find(|b| (b.day, b.part) == (timing.day, timing.part))
+        else {
+            continue;
+        };
+        let phases = [
+            ("parse", base.parse, timing.parse.median),
+            ("solve", base.solve, timing.solve.median),
+        ];
+        for (phase, before, after) in phases {
+            if after.as_secs_f64() > before.as_secs_f64() * limit {
+                found.push(Regression {
+                    day: timing.day,
+                    part: timing.part,
+                    phase,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Baseline, Failed, Stats, Timing};
+    use crate::solution::{self, Params, Part};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn timing(day: u8, parse: u64, solve: u64) -> Timing {
+        let stats = |n| Stats {
+            min: ms(n),
+            median: ms(n),
+            max: ms(n),
+        };
+        Timing {
+            day,
+            part: Part::One,
+            runs: 1,
+            parse: stats(parse),
+            solve: stats(solve),
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let odd = Stats::from_samples(&mut [ms(5), ms(1), ms(3)]);
+        assert_eq!((odd.min, odd.median, odd.max), (ms(1), ms(3), ms(5)));
+        let even = Stats::from_samples(&mut [ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!(even.median, ms(3));
+    }
+
+    #[test]
+    fn times_a_day() {
+        let day9 = solution::find(9).unwrap();
+        let timing = super::time(day9, Part::Two, "0 3 6 9", &Params::default(), 3).unwrap();
+        assert_eq!((timing.day, timing.part, timing.runs), (9, Part::Two, 3));
+        assert!(timing.parse.min <= timing.parse.median && timing.parse.median <= timing.parse.max);
+        assert!(super::time(day9, Part::One, "0 x", &Params::default(), 3).is_err());
+    }
+
+    #[test]
+    fn baseline() {
+        let timings = [timing(1, 2, 10), timing(2, 1, 1)];
+        let saved = super::parse_baseline(&super::save(&timings)).unwrap();
+        assert_eq!(
+            saved[0],
+            Baseline {
+                day: 1,
+                part: Part::One,
+                parse: ms(2),
+                solve: ms(10),
+            }
+        );
+        assert_eq!(
+            super::parse_baseline("1 1 5").unwrap_err().to_string(),
+            "<input>:1:1: unexpected input (Count)"
+        );
+
+        let slower = [timing(1, 2, 12), timing(2, 2, 1), timing(3, 100, 100)];
+        let found = super::regressions(&slower, &saved, 10.0);
+        let found: Vec<_> = found.iter().map(|r| (r.day, r.phase)).collect();
+        assert_eq!(found, [(1, "solve"), (2, "parse")]);
+        assert!(super::regressions(&slower, &saved, 200.0).is_empty());
+    }
+
+    #[test]
+    fn failures() {
+        let failed = [Failed {
+            day: 1,
+            part: Part::Two,
+            reason: "timed out".to_string(),
+        }];
+        let timings = [timing(1, 2, 3), timing(2, 1, 1)];
+        let table = super::table(&timings, &failed);
+        let rows: Vec<Vec<_>> = table
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        assert_eq!(rows[3], ["1", "2", "FAILED", "-", "-", "-"]);
+        assert_eq!(rows[4][..3], ["2", "1", "parse"]);
+        let json = super::json(&timings, &failed);
+        assert!(json.contains("{\"day\": 1, \"part\": 2, \"error\": \"timed out\"},\n"));
+    }
+
+    #[test]
+    fn json() {
+        let json = super::json(&[timing(1, 2, 3)], &[]);
+        assert_eq!(
+            json,
+            "[\n  {\"day\": 1, \"part\": 1, \"runs\": 1, \
+             \"parse\": {\"min_ns\": 2000000, \"median_ns\": 2000000, \"max_ns\": 2000000}, \
+             \"solve\": {\"min_ns\": 3000000, \"median_ns\": 3000000, \"max_ns\": 3000000}}\n]\n"
+        );
+    }
+}
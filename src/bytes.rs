@@ -0,0 +1,61 @@
+//! Random bytes and their hex, for the salts, nonces and digests that
+//! answers and vaults store as text.
+
+use nom::error::ErrorKind;
+
+use crate::error::PuzzleError;
+
+/// `N` bytes from the operating system's random source.
+pub fn random<const N: usize>() -> Result<[u8; N], PuzzleError> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| PuzzleError::invalid(format!("no randomness to draw on: {e}")))?;
+    Ok(bytes)
+}
+
+/// `bytes` as lowercase hex.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Decodes the hex in `field`, a subslice of `text`.
+pub fn unhex(text: &str, field: &str) -> Result<Vec<u8>, PuzzleError> {
+    if !field.is_ascii() || !field.len().is_multiple_of(2) {
+        return Err(PuzzleError::parse(text, field, ErrorKind::HexDigit));
+    }
+    (0..field.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&field[i..i + 2], 16)
+                .map_err(|_| PuzzleError::parse(text, &field[i..], ErrorKind::HexDigit))
+        })
+        .collect()
+}
+
+/// Decodes the hex in `field`, which must be `N` bytes long.
+pub fn unhex_exact<const N: usize>(text: &str, field: &str) -> Result<[u8; N], PuzzleError> {
+    unhex(text, field)?
+        .try_into()
+        .map_err(|_| PuzzleError::parse(text, field, ErrorKind::LengthValue))
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn hex() {
+        assert_eq!(super::hex(&[0, 0xab, 0x10]), "00ab10");
+        let text = "key 00ab10";
+        assert_eq!(super::unhex(text, &text[4..]).unwrap(), [0, 0xab, 0x10]);
+        assert_eq!(
+            super::unhex_exact::<2>(text, &text[4..])
+                .unwrap_err()
+                .to_string(),
+            "<input>:1:5: unexpected input (Length followed by value)"
+        );
+        assert_eq!(
+            super::unhex(text, &text[..4]).unwrap_err().to_string(),
+            "<input>:1:1: unexpected input (Hexadecimal Digit)"
+        );
+        assert_ne!(super::random::<16>().unwrap(), [0; 16]);
+    }
+}
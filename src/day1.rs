@@ -22,8 +22,6 @@ In this example, the calibration values of these four lines are 12, 38, 15, and
 
 Consider your entire calibration document. What is the sum of all of the calibration values?
 
-Your puzzle answer was 56397.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -42,113 +40,781 @@ In this example, the calibration values are 29, 83, 13, 24, 42, 14, and 76. Addi
 
 What is the sum of all of the calibration values?
 
-Your puzzle answer was 55701.
-
 
 */
 
-use std::fs::read_to_string;
-
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{anychar, char};
-use nom::combinator::{map, peek};
-use nom::multi::many1;
-use nom::sequence::terminated;
-use nom::IResult;
-
-pub fn part1(file_name: &str) -> u32 {
-    read_to_string(file_name)
-        .unwrap()
-        .split_whitespace()
-        .map(parse_int_line)
-        .sum()
-}
-
-fn parse_int_line(line: &str) -> u32 {
-    let digits: Vec<_> = line.chars().filter(|c| c.is_numeric()).collect();
-    let num_str = if digits.len() == 1 {
-        format!("{}{}", digits[0], digits[0])
-    } else {
-        format!("{}{}", digits[0], digits[digits.len() - 1])
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use memchr::memchr;
+use nom::error::ErrorKind;
+
+use crate::error::PuzzleError;
+use crate::log::{self, trace, Level};
+use crate::report::{self, json_string, Align};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
+/// The calibration document: the puzzle input, read a line at a time as it
+/// is solved.
+pub struct Document<'a> {
+    input: &'a str,
+}
+
+pub fn parse(input: &str) -> Result<Document<'_>, PuzzleError> {
+    Ok(Document { input })
+}
+
+pub fn part1(document: &Document) -> Result<u64, PuzzleError> {
+    sum_lines(document.input.as_bytes(), &Vocabulary::digits())
+}
+
+pub fn part2(document: &Document) -> Result<u64, PuzzleError> {
+    part2_with(document, &Vocabulary::english())
+}
+
+/// Part 2, spelling the digits with `vocabulary` instead of in English.
+pub fn part2_with(document: &Document, vocabulary: &Vocabulary) -> Result<u64, PuzzleError> {
+    sum_lines(document.input.as_bytes(), vocabulary)
+}
+
+/// Sums the calibration values of the lines of `input`, skipping blank ones.
+/// Each line is read from the front up to its first digit and from the back
+/// up to its last, in place.
+pub fn sum_lines(input: &[u8], vocabulary: &Vocabulary) -> Result<u64, PuzzleError> {
+    let mut tally = Tally::new();
+    let mut rest = input;
+    while let Some(end) = memchr(b'\n', rest) {
+        tally.add(&rest[..end], vocabulary)?;
+        rest = &rest[end + 1..];
+    }
+    tally.add(rest, vocabulary)?;
+    Ok(tally.sum)
+}
+
+/// Like [`sum_lines`], but reading `reader` a buffer at a time, so that the
+/// document never has to fit in memory. Only a line that straddles two
+/// buffers is copied. Errors name `source`, where the reader reads from.
+pub fn sum_reader(
+    reader: impl BufRead,
+    vocabulary: &Vocabulary,
+    source: &str,
+) -> Result<u64, PuzzleError> {
+    tally_reader(reader, vocabulary, source).map_err(|e| e.in_file(source))
+}
+
+fn tally_reader(
+    mut reader: impl BufRead,
+    vocabulary: &Vocabulary,
+    source: &str,
+) -> Result<u64, PuzzleError> {
+    let mut tally = Tally::new();
+    let mut straddling = Vec::new();
+    loop {
+        let buffer = reader.fill_buf().map_err(|error| PuzzleError::Io {
+            file: source.to_string(),
+            error,
+        })?;
+        if buffer.is_empty() {
+            break;
+        }
+        let read = buffer.len();
+        let mut rest = buffer;
+        while let Some(end) = memchr(b'\n', rest) {
+            if straddling.is_empty() {
+                tally.add(&rest[..end], vocabulary)?;
+            } else {
+                straddling.extend_from_slice(&rest[..end]);
+                tally.add(&straddling, vocabulary)?;
+                straddling.clear();
+            }
+            rest = &rest[end + 1..];
+        }
+        straddling.extend_from_slice(rest);
+        reader.consume(read);
+    }
+    tally.add(&straddling, vocabulary)?;
+    Ok(tally.sum)
+}
+
+/// The running total of a document's calibration values.
+struct Tally {
+    sum: u64,
+    lines: usize,
+    /// Whether to trace each line, looked up once rather than per line.
+    tracing: bool,
+}
+
+impl Tally {
+    fn new() -> Tally {
+        Tally {
+            sum: 0,
+            lines: 0,
+            tracing: log::enabled(Level::Trace, module_path!()),
+        }
+    }
+
+    fn add(&mut self, line: &[u8], vocabulary: &Vocabulary) -> Result<(), PuzzleError> {
+        self.lines += 1;
+        let line = line.trim_ascii();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let (Some(first), Some(last)) = (vocabulary.first(line), vocabulary.last(line)) else {
+            return Err(PuzzleError::Parse {
+                file: None,
+                line: self.lines,
+                column: 1,
+                kind: ErrorKind::Digit,
+            });
+        };
+        let value = first.digit * 10 + last.digit;
+        if self.tracing {
+            trace!("{}: {}", String::from_utf8_lossy(line), value);
+        }
+        self.sum += u64::from(value);
+        Ok(())
+    }
+}
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const ORDINALS: [&str; 9] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+];
+
+/// The vocabularies that can be named in the `words` parameter.
+pub const VOCABULARIES: [(&str, [&str; 9]); 3] = [
+    ("english", ENGLISH),
+    ("german", GERMAN),
+    ("ordinals", ORDINALS),
+];
+
+/// Words that spell digits, recognised wherever they occur in a line, even
+/// when they overlap as in "eightwo".
+///
+/// The words and digits are compiled into Aho-Corasick automata, one reading
+/// forwards and one reading the words backwards from the end of a line, so
+/// finding a line's first and last digits takes one table lookup per byte
+/// whatever the size of the vocabulary, and usually reads only a few bytes
+/// at either end.
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    forward: Automaton,
+    backward: Automaton,
+    /// The length of the longest word, or 0 for no words at all.
+    longest: usize,
+}
+
+impl Vocabulary {
+    /// Builds the recognizer for `words`, each spelling a digit from 0 to 9.
+    pub fn new<'w>(words: impl IntoIterator<Item = (&'w str, u32)>) -> Result<Self, PuzzleError> {
+        Vocabulary::build(words).map_err(PuzzleError::invalid)
+    }
+
+    fn build<'w>(words: impl IntoIterator<Item = (&'w str, u32)>) -> Result<Self, String> {
+        // Digits as such go first, so they win over words spelled the same.
+        let mut forward: Vec<(Vec<u8>, Entry)> = (0..10)
+            .map(|digit| {
+                let entry = Entry {
+                    digit,
+                    len: 1,
+                    spelled: false,
+                };
+                (vec![b'0' + digit as u8], entry)
+            })
+            .collect();
+        let mut digits = HashMap::new();
+        for (word, digit) in words {
+            if word.is_empty() || digit > 9 {
+                return Err(format!("`{word}` cannot spell the digit {digit}"));
+            }
+            if *digits.entry(word).or_insert(digit) != digit {
+                return Err(format!("`{word}` spells both {} and {digit}", digits[word]));
+            }
+            let entry = Entry {
+                digit,
+                len: word.len(),
+                spelled: true,
+            };
+            forward.push((word.as_bytes().to_vec(), entry));
+        }
+        let backward: Vec<_> = forward
+            .iter()
+            .map(|(word, entry)| (word.iter().rev().copied().collect(), *entry))
+            .collect();
+        Ok(Vocabulary {
+            longest: digits.keys().map(|word| word.len()).max().unwrap_or(0),
+            forward: Automaton::new(&forward),
+            backward: Automaton::new(&backward),
+        })
+    }
+
+    /// No words: only digits as such count, as in part 1.
+    pub fn digits() -> Self {
+        Self::new([]).expect("no words to clash")
+    }
+
+    /// The nine English words, as the puzzle spells them.
+    pub fn english() -> Self {
+        Self::numbered(&ENGLISH)
+    }
+
+    fn numbered(words: &[&str; 9]) -> Self {
+        Self::new(words.iter().copied().zip(1..)).expect("distinct, non-empty words")
+    }
+
+    /// The first and last digits in `line`, in digits or in words, by where
+    /// they start.
+    pub fn first_and_last(&self, line: &str) -> Option<(u32, u32)> {
+        self.ends(line)
+            .map(|(first, last)| (first.digit, last.digit))
+    }
+
+    /// Like [`Vocabulary::first_and_last`], with where each digit was found.
+    pub fn ends<'a>(&self, line: &'a str) -> Option<(Found<'a>, Found<'a>)> {
+        let found = |hit: Hit| Found {
+            digit: hit.digit,
+            offset: hit.offset,
+            // Words are whole UTF-8 sequences, so they match on boundaries.
+            word: (hit.len > 0).then(|| &line[hit.offset..hit.offset + hit.len]),
+        };
+        let bytes = line.as_bytes();
+        Some((found(self.first(bytes)?), found(self.last(bytes)?)))
+    }
+
+    /// Every digit in `line`, in digits or in words, as its byte offset and
+    /// value, overlapping words included: "twone" holds a 2 at 0 and a 1 at
+    /// 2. They come in the order they end, longest first where several end
+    /// together.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = (usize, u32)> + 'a {
+        let automaton = &self.forward;
+        line.bytes()
+            .enumerate()
+            .scan(0, move |row, (i, byte)| {
+                *row = automaton.step(*row, byte);
+                Some((i, *row))
+            })
+            .flat_map(move |(i, row)| {
+                automaton
+                    .found(row)
+                    .iter()
+                    .map(move |entry| (i + 1 - entry.len, entry.digit))
+            })
+    }
+
+    /// The digit that starts first in `line`.
+    fn first(&self, line: &[u8]) -> Option<Hit> {
+        if self.longest == 0 {
+            return line
+                .iter()
+                .position(u8::is_ascii_digit)
+                .map(|i| Hit::digit(line, i));
+        }
+        // Matches come in the order they end, so one that ends later may
+        // still start earlier, or as early but longer, as long as it ends
+        // within the longest word of the best so far.
+        let mut best: Option<Hit> = None;
+        let mut limit = line.len();
+        let mut row = 0;
+        let mut i = 0;
+        while i < limit {
+            row = self.forward.step(row, line[i]);
+            for entry in self.forward.found(row) {
+                let hit = entry.hit(i + 1 - entry.len);
+                if best.is_none_or(|best| (hit.offset, best.len) < (best.offset, hit.len)) {
+                    best = Some(hit);
+                    limit = limit.min(hit.offset + self.longest);
+                }
+            }
+            i += 1;
+        }
+        best
+    }
+
+    /// The digit that starts last in `line`.
+    fn last(&self, line: &[u8]) -> Option<Hit> {
+        if self.longest == 0 {
+            return line
+                .iter()
+                .rposition(u8::is_ascii_digit)
+                .map(|i| Hit::digit(line, i));
+        }
+        // Read backwards, a match ends where the word starts, so the first
+        // one found is the one that starts last.
+        let mut row = 0;
+        for (i, &byte) in line.iter().enumerate().rev() {
+            row = self.backward.step(row, byte);
+            if let Some(entry) = self.backward.found(row).first() {
+                return Some(entry.hit(i));
+            }
+        }
+        None
+    }
+}
+
+/// Where a digit was found in a line.
+#[derive(Debug, Clone, Copy)]
+struct Hit {
+    digit: u32,
+    offset: usize,
+    /// The length of the word, or 0 for a digit as such.
+    len: usize,
+}
+
+impl Hit {
+    fn digit(line: &[u8], offset: usize) -> Hit {
+        Hit {
+            digit: u32::from(line[offset] - b'0'),
+            offset,
+            len: 0,
+        }
+    }
+}
+
+/// A word or digit the automata recognise.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    digit: u32,
+    len: usize,
+    /// Whether it is a word rather than a digit as such.
+    spelled: bool,
+}
+
+impl Entry {
+    fn hit(&self, offset: usize) -> Hit {
+        Hit {
+            digit: self.digit,
+            offset,
+            len: if self.spelled { self.len } else { 0 },
+        }
+    }
+}
+
+/// An Aho-Corasick automaton over bytes, as a table of transitions.
+#[derive(Debug, Clone)]
+struct Automaton {
+    /// Bytes in none of the words share class 0; the others get one each,
+    /// which keeps the table small enough to stay in the cache.
+    classes: [u16; 256],
+    stride: usize,
+    /// The transitions, failure links already followed. A state is the
+    /// offset of its row, with [`Automaton::FOUND`] set if words end there.
+    next: Vec<u32>,
+    /// The words that end in each state, longest first.
+    found: Vec<Vec<Entry>>,
+}
+
+impl Automaton {
+    const FOUND: u32 = 1 << 31;
+
+    fn new(words: &[(Vec<u8>, Entry)]) -> Automaton {
+        let mut classes = [0; 256];
+        let mut stride = 1;
+        for (word, _) in words {
+            for &byte in word {
+                if classes[byte as usize] == 0 {
+                    classes[byte as usize] = stride as u16;
+                    stride += 1;
+                }
+            }
+        }
+
+        // The trie of the words, with states numbered from 0.
+        let mut next = vec![0; stride];
+        let mut found = vec![Vec::new()];
+        for (word, entry) in words {
+            let mut state = 0;
+            for &byte in word {
+                let slot = state * stride + classes[byte as usize] as usize;
+                if next[slot] == 0 {
+                    next[slot] = found.len();
+                    next.resize(next.len() + stride, 0);
+                    found.push(Vec::new());
+                }
+                state = next[slot];
+            }
+            if found[state].is_empty() {
+                found[state].push(*entry);
+            }
+        }
+
+        // Breadth first, so every state's failure state is complete before
+        // its transitions are filled in from it.
+        let mut fail = vec![0; found.len()];
+        let mut queue: VecDeque<usize> = next[..stride]
+            .iter()
+            .filter(|&&child| child != 0)
+            .copied()
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            for class in 0..stride {
+                let child = next[state * stride + class];
+                let fallback = next[fail[state] * stride + class];
+                if child == 0 {
+                    next[state * stride + class] = fallback;
+                } else {
+                    fail[child] = fallback;
+                    let inherited = found[fallback].clone();
+                    found[child].extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        let next = next
+            .into_iter()
+            .map(|state| {
+                let flag = if found[state].is_empty() {
+                    0
+                } else {
+                    Self::FOUND
+                };
+                (state * stride) as u32 | flag
+            })
+            .collect();
+        Automaton {
+            classes,
+            stride,
+            next,
+            found,
+        }
+    }
+
+    /// The state after `state` on `byte`.
+    fn step(&self, state: u32, byte: u8) -> u32 {
+        self.next[(state & !Self::FOUND) as usize + self.classes[byte as usize] as usize]
+    }
+
+    /// The words that end in `state`.
+    fn found(&self, state: u32) -> &[Entry] {
+        if state & Self::FOUND == 0 {
+            return &[];
+        }
+        &self.found[(state & !Self::FOUND) as usize / self.stride]
+    }
+}
+
+/// A digit found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Found<'a> {
+    pub digit: u32,
+    /// The byte offset of the digit, or of the first letter of its word.
+    pub offset: usize,
+    /// The word that spelled the digit, or `None` for a digit as such.
+    pub word: Option<&'a str>,
+}
+
+/// Reads the `words` parameter: a comma-separated list of named
+/// [`VOCABULARIES`] and `word=digit` pairs, such as `english,zero=0`.
+impl FromStr for Vocabulary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = Vec::new();
+        for item in s.split(',').map(str::trim) {
+            if let Some((word, digit)) = item.split_once('=') {
+                let digit = digit
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("`{digit}` is not a digit"))?;
+                words.push((word.trim(), digit));
+            } else if let Some((_, named)) = VOCABULARIES.iter().find(|(name, _)| *name == item) {
+                words.extend(named.iter().copied().zip(1..));
+            } else {
+                return Err(format!("no vocabulary called `{item}`"));
+            }
+        }
+        Vocabulary::build(words)
+    }
+}
+
+/// How one line of the document was read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    /// The 1-based line number in the input.
+    pub line_number: usize,
+    pub line: &'a str,
+    /// The first and last digits, or `None` for a line without any.
+    pub ends: Option<(Found<'a>, Found<'a>)>,
+}
+
+impl Explanation<'_> {
+    /// The calibration value, if the line has digits to make one.
+    pub fn value(&self) -> Option<u32> {
+        self.ends.map(|(first, last)| first.digit * 10 + last.digit)
+    }
+}
+
+/// Reads every line as part 2 does with `vocabulary`, keeping the details
+/// that part 2 throws away. An empty vocabulary reads them as part 1 does.
+pub fn explain<'a>(document: &Document<'a>, vocabulary: &Vocabulary) -> Vec<Explanation<'a>> {
+    document
+        .input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_ascii()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_number, line)| Explanation {
+            line_number,
+            line,
+            ends: vocabulary.ends(line),
+        })
+        .collect()
+}
+
+/// Lays the explanations out as a table, flagging lines without digits.
+pub fn explain_table(explanations: &[Explanation]) -> String {
+    let describe = |found: &Found| match found.word {
+        Some(word) => format!("{} @{} ({word})", found.digit, found.offset),
+        None => format!("{} @{}", found.digit, found.offset),
     };
-    num_str.parse().unwrap()
-}
-
-pub fn part2(file_name: &str) -> u32 {
-    read_to_string(file_name)
-        .unwrap()
-        .split_whitespace()
-        .map(parse_word_line)
-        .sum()
-}
-
-fn parse_word(input: &str) -> Option<u32> {
-    match input {
-        "on" => Some(1),
-        "tw" => Some(2),
-        "thre" => Some(3),
-        "four" => Some(4),
-        "fiv" => Some(5),
-        "six" => Some(6),
-        "seve" => Some(7),
-        "eigh" => Some(8),
-        "nin" => Some(9),
-        _ => None,
-    }
-}
-
-fn parse_word_digits(input: &str) -> IResult<&str, Vec<Option<u32>>> {
-    many1(alt((
-        map(
-            alt((
-                terminated(tag("on"), peek(char('e'))),
-                terminated(tag("tw"), peek(char('o'))),
-                terminated(tag("thre"), peek(char('e'))),
-                tag("four"),
-                terminated(tag("fiv"), peek(char('e'))),
-                tag("six"),
-                terminated(tag("seve"), peek(char('n'))),
-                terminated(tag("eigh"), peek(char('t'))),
-                terminated(tag("nin"), peek(char('e'))),
-            )),
-            parse_word,
-        ),
-        map(anychar, |c| c.to_digit(10)),
-    )))(input)
+    let rows: Vec<[String; 5]> = explanations
+        .iter()
+        .map(|e| {
+            let (first, last, value) = match (&e.ends, e.value()) {
+                (Some((first, last)), Some(value)) => {
+                    (describe(first), describe(last), value.to_string())
+                }
+                _ => ("-".to_string(), "-".to_string(), "NO DIGITS".to_string()),
+            };
+            [
+                e.line_number.to_string(),
+                e.line.to_string(),
+                first,
+                last,
+                value,
+            ]
+        })
+        .collect();
+    report::table(
+        ["line", "text", "first", "last", "value"],
+        [
+            Align::Right,
+            Align::Left,
+            Align::Left,
+            Align::Left,
+            Align::Right,
+        ],
+        &rows,
+    )
 }
 
-fn parse_word_line(line: &str) -> u32 {
-    let digits: Vec<_> = parse_word_digits(line)
-        .unwrap()
-        .1
-        .into_iter()
-        .flatten()
+/// The explanations as a JSON array, with `null` for what a line without
+/// digits lacks.
+pub fn explain_json(explanations: &[Explanation]) -> String {
+    let found = |found: Option<&Found>| match found {
+        Some(found) => format!(
+            "{{\"digit\": {}, \"offset\": {}, \"word\": {}}}",
+            found.digit,
+            found.offset,
+            found.word.map_or("null".to_string(), json_string)
+        ),
+        None => "null".to_string(),
+    };
+    let entries: Vec<String> = explanations
+        .iter()
+        .map(|e| {
+            format!(
+                "  {{\"line\": {}, \"text\": {}, \"first\": {}, \"last\": {}, \"value\": {}}}",
+                e.line_number,
+                json_string(e.line),
+                found(e.ends.as_ref().map(|(first, _)| first)),
+                found(e.ends.as_ref().map(|(_, last)| last)),
+                e.value().map_or("null".to_string(), |v| v.to_string())
+            )
+        })
         .collect();
+    report::json_array(&entries)
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    part2(&parse(input)?)
+}
+
+/// Solves `part` as [`Day1`] does, streaming the document from `reader`
+/// through [`sum_reader`] rather than reading it whole first.
+pub fn solve_reader(
+    reader: impl BufRead,
+    source: &str,
+    part: Part,
+    params: &Params,
+) -> Result<Answer, PuzzleError> {
+    let vocabulary = match part {
+        Part::One => Vocabulary::digits(),
+        Part::Two => params.get_or("words", Vocabulary::english())?,
+    };
+    sum_reader(reader, &vocabulary, source).map(Answer::from)
+}
+
+pub struct Day1;
 
-    eprintln!("{}: [{},{}]", line, digits[0], digits[digits.len() - 1]);
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        &["words"]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
 
-    let line_res = format!("{}{}", digits[0], digits[digits.len() - 1])
-        .parse()
-        .unwrap();
-    line_res
+impl Parsed for Document<'_> {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2_with(self, &params.get_or("words", Vocabulary::english())?)?.into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const EXAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
     #[test]
     fn part1() {
-        assert_eq!(super::part1("src/day1_test_input.txt"), 142);
-        assert_eq!(super::part1("src/day1_input.txt"), 56397)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 142);
     }
 
     #[test]
     fn part2() {
+        assert_eq!(super::solve_part2(EXAMPLE2).unwrap(), 281);
+    }
+
+    #[test]
+    fn reads_across_buffers() {
+        let english = super::Vocabulary::english();
+        for capacity in [1, 3, 64] {
+            let reader = std::io::BufReader::with_capacity(capacity, EXAMPLE2.as_bytes());
+            assert_eq!(super::sum_reader(reader, &english, "-").unwrap(), 281);
+        }
+    }
+
+    #[test]
+    fn skips_blank_lines_and_carriage_returns() {
+        let crlf = EXAMPLE.replace('\n', "\r\n\r\n");
+        assert_eq!(
+            super::sum_lines(crlf.as_bytes(), &super::Vocabulary::digits()).unwrap(),
+            142
+        );
+    }
+
+    #[test]
+    fn reader_errors_name_the_source() {
+        let english = super::Vocabulary::english();
+        assert_eq!(
+            super::sum_reader(&b"1two\n\nxyz\n"[..], &english, "<stdin>")
+                .unwrap_err()
+                .to_string(),
+            "<stdin>:3:1: unexpected input (Digit)"
+        );
+    }
+
+    #[test]
+    fn solve_reader_takes_the_words() {
+        let mut params = super::Params::default();
+        params.set("words", "german");
         assert_eq!(
-            super::part2("src/day1_2_test_input.txt"),
-            281,
-            "Test input failed"
+            super::solve_reader(&b"1\nzweix\n"[..], "-", super::Part::Two, &params).unwrap(),
+            super::Answer(11 + 22)
         );
-        assert_eq!(super::part2("src/day1_input.txt"), 55701)
+    }
+
+    #[test]
+    fn word_found_later_can_start_first() {
+        // "abcd" is found after "bc" but starts first.
+        let nested = super::Vocabulary::new([("abcd", 1), ("bc", 2), ("cd", 3)]).unwrap();
+        assert_eq!(nested.first_and_last("xabcdx"), Some((1, 3)));
+    }
+
+    #[test]
+    fn explain() {
+        let document = super::parse("two1nine\n\nxyz\n7pqrst\"six\"teen").unwrap();
+        let explanations = super::explain(&document, &super::Vocabulary::english());
+        assert_eq!(
+            explanations.iter().map(|e| e.value()).collect::<Vec<_>>(),
+            [Some(29), None, Some(76)]
+        );
+        assert_eq!(
+            super::explain_table(&explanations),
+            "\
+line  text             first       last             value
+   1  two1nine         2 @0 (two)  9 @4 (nine)         29
+   3  xyz              -           -            NO DIGITS
+   4  7pqrst\"six\"teen  7 @0        6 @7 (six)          76
+"
+        );
+        assert_eq!(
+            super::explain_json(&explanations[1..]),
+            "[\n  {\"line\": 3, \"text\": \"xyz\", \"first\": null, \"last\": null, \"value\": null},\n  \
+             {\"line\": 4, \"text\": \"7pqrst\\\"six\\\"teen\", \
+             \"first\": {\"digit\": 7, \"offset\": 0, \"word\": null}, \
+             \"last\": {\"digit\": 6, \"offset\": 7, \"word\": \"six\"}, \"value\": 76}\n]\n"
+        );
+
+        // A no-break space is not blank to the solver, so it is not here.
+        let document = super::parse("1\n\u{a0}\n").unwrap();
+        let explanations = super::explain(&document, &super::Vocabulary::digits());
+        assert_eq!(explanations[1].line_number, 2);
+        assert_eq!(explanations[1].value(), None);
+        assert!(super::part1(&document).is_err());
+    }
+
+    #[test]
+    fn vocabularies() {
+        let english = super::Vocabulary::english();
+        assert_eq!(english.first_and_last("eightwo"), Some((8, 2)));
+        assert_eq!(english.first_and_last("oneight"), Some((1, 8)));
+        assert_eq!(english.first_and_last("sevenine"), Some((7, 9)));
+        assert_eq!(english.first_and_last("xyz"), None);
+        let matches = |line| english.matches(line).collect::<Vec<_>>();
+        assert_eq!(matches("twone"), [(0, 2), (2, 1)]);
+        assert_eq!(matches("eightwo"), [(0, 8), (4, 2)]);
+        assert_eq!(matches("7sevenine"), [(0, 7), (1, 7), (5, 9)]);
+        assert_eq!(matches("xyz"), []);
+
+        let vocabulary: super::Vocabulary = "german,ordinals,zero=0".parse().unwrap();
+        assert_eq!(vocabulary.first_and_last("zerofünfzig"), Some((0, 5)));
+        assert_eq!(
+            vocabulary.first_and_last("seventhreeinsecond"),
+            Some((7, 2))
+        );
+        assert_eq!(vocabulary.first_and_last("a4bneunx"), Some((4, 9)));
+        let document = super::parse("vierx3\nzweight").unwrap();
+        assert_eq!(super::part2_with(&document, &vocabulary).unwrap(), 43 + 22);
+
+        assert!("klingon".parse::<super::Vocabulary>().is_err());
+        assert!(super::Vocabulary::new([])
+            .unwrap()
+            .first_and_last("one2")
+            .is_some());
+        assert!("one=1,one=2".parse::<super::Vocabulary>().is_err());
+        assert!("ten=10".parse::<super::Vocabulary>().is_err());
     }
 }
@@ -93,8 +93,6 @@ Here are the distances for each tile on that loop:
 23...
 Find the single giant loop starting at S. How many steps along the loop does it take to get from the starting position to the point farthest from the starting position?
 
-Your puzzle answer was 6754.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -192,10 +190,13 @@ Figure out whether you have time to search for the nest by calculating the area
 */
 
 use std::collections::HashSet;
-use std::fs::read_to_string;
+
+use crate::error::PuzzleError;
+use crate::grid::{Grid, Point};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Pipe {
+pub enum Pipe {
     Start,
     Vertical,
     Horizontal,
@@ -203,114 +204,64 @@ enum Pipe {
     NorthWest,
     SouthWest,
     SouthEast,
-    NoPipe,
+    Ground,
 }
 
-struct Cell {
-    x: isize,
-    y: isize,
-    pipe: Pipe,
+pub fn part1(grid: &Grid<Pipe>) -> Result<u64, PuzzleError> {
+    Ok(main_loop(grid)?.1 / 2)
 }
 
-pub fn part1(file_name: &str) -> u64 {
-    let grid = parse_grid(file_name);
-    let (sx, sy) = find_start(&grid);
-    let sx = sx as isize;
-    let sy = sy as isize;
-    [(sx + 1, sy), (sx, sy + 1), (sx - 1, sy), (sx, sy - 1)]
+pub fn part2(grid: &Grid<Pipe>) -> Result<usize, PuzzleError> {
+    let surrounding: HashSet<Point> = main_loop(grid)?.0.into_iter().collect();
+    use Pipe::*;
+    // A tile is enclosed when a ray to the right crosses the loop an odd
+    // number of times, counting only pipes that reach down from the row.
+    Ok(grid
         .iter()
-        .filter_map(|&(x, y)| {
-            if x < 0
-                || y < 0
-                || x >= grid[0].len() as isize
-                || y >= grid.len() as isize
-                || !connect(
-                    &Cell {
-                        x,
-                        y,
-                        pipe: grid[y as usize][x as usize],
-                    },
-                    sx,
-                    sy,
-                )
-            {
-                None
-            } else {
-                walk(x, y, &grid, &mut HashSet::new())
-            }
+        .filter(|&((x, y), _)| {
+            !surrounding.contains(&(x, y))
+                && (x..grid.width())
+                    .filter(|&cx| {
+                        surrounding.contains(&(cx, y))
+                            && ![NorthWest, NorthEast, Horizontal].contains(&grid[(cx, y)])
+                    })
+                    .count()
+                    % 2
+                    == 1
         })
-        .max_by_key(|(_, s)| *s)
-        .unwrap()
-        .1
-        / 2
+        .count())
 }
 
-pub fn part2(file_name: &str) -> usize {
-    let grid = parse_grid(file_name);
-    let (sx, sy) = find_start(&grid);
-    let sx = sx as isize;
-    let sy = sy as isize;
-    let the_loop = [(sx + 1, sy), (sx, sy + 1), (sx - 1, sy), (sx, sy - 1)]
-        .iter()
-        .filter_map(|&(x, y)| {
-            if x < 0
-                || y < 0
-                || x >= grid[0].len() as isize
-                || y >= grid.len() as isize
-                || !connect(
-                    &Cell {
-                        x,
-                        y,
-                        pipe: grid[y as usize][x as usize],
-                    },
-                    sx,
-                    sy,
-                )
-            {
-                None
-            } else {
-                walk(x, y, &grid, &mut HashSet::new())
-            }
-        })
-        .max_by_key(|(_, s)| *s)
-        .unwrap()
-        .0;
-    let surrounding = HashSet::<(isize, isize)>::from_iter(the_loop.iter().cloned());
-    let mut enclosed = HashSet::new();
-    for x in 0..grid[0].len() {
-        for y in 0..grid.len() {
-            if surrounding.contains(&(x as isize, y as isize)) {
-                continue;
-            }
-            let mut curr_x = x as isize;
-            let mut intersections = Vec::new();
-            use Pipe::*;
-            loop {
-                if curr_x < 0 || curr_x >= grid[0].len() as isize {
-                    break;
-                } else if surrounding.contains(&(curr_x, y as isize))
-                    && ![NorthWest, NorthEast, Horizontal].contains(&grid[y][curr_x as usize])
-                {
-                    intersections.push(curr_x);
-                    curr_x += 1;
-                    continue;
-                } else {
-                    curr_x += 1;
-                    continue;
-                }
-            }
-            if intersections.len() % 2 == 1 {
-                enclosed.insert((x, y, intersections));
-            }
+/// The tiles of the longest loop through the start tile, and its length.
+fn main_loop(grid: &Grid<Pipe>) -> Result<(Vec<Point>, u64), PuzzleError> {
+    let start = grid
+        .find(|&pipe| pipe == Pipe::Start)
+        .ok_or_else(|| PuzzleError::invalid("no start tile"))?;
+    let mut loops = Vec::new();
+    for point in grid.neighbours4(start) {
+        if connect(grid[point], point, start)? {
+            loops.extend(walk(point, start, grid)?);
         }
     }
+    loops
+        .into_iter()
+        .max_by_key(|(_, s)| *s)
+        .ok_or_else(|| PuzzleError::invalid("no loop through the start tile"))
+}
 
-    enclosed.len()
+fn second_start((x, y): Point) -> PuzzleError {
+    PuzzleError::invalid(format!(
+        "a second start tile on line {}, column {}",
+        y + 1,
+        x + 1
+    ))
 }
 
-fn to_pipe(c: char) -> Pipe {
+/// Maps a tile to its pipe. The puzzle's sketches mark tiles inside and
+/// outside the loop with `I` and `O`, which are ground as far as pipes go.
+fn to_pipe(c: char) -> Option<Pipe> {
     use Pipe::*;
-    match c {
+    Some(match c {
         'S' => Start,
         '|' => Vertical,
         '-' => Horizontal,
@@ -318,136 +269,253 @@ fn to_pipe(c: char) -> Pipe {
         'J' => NorthWest,
         '7' => SouthWest,
         'F' => SouthEast,
-        _ => NoPipe,
-    }
+        '.' | 'I' | 'O' => Ground,
+        _ => return None,
+    })
 }
 
+/// Follows the pipes from `first`, a neighbour of the start tile, until they
+/// lead back to the start, giving the tiles of the loop and its length, or
+/// `None` if they lead anywhere else.
 fn walk(
-    x: isize,
-    y: isize,
-    grid: &Vec<Vec<Pipe>>,
-    visited: &mut HashSet<(isize, isize)>,
-) -> Option<(Vec<(isize, isize)>, u64)> {
-    if visited.contains(&(x, y)) {
-        return None;
-    }
-    visited.insert((x, y));
-    if x < 0 || x >= grid[0].len() as isize || y < 0 || y >= grid.len() as isize {
-        visited.remove(&(x, y));
-        return None;
-    }
+    first: Point,
+    start: Point,
+    grid: &Grid<Pipe>,
+) -> Result<Option<(Vec<Point>, u64)>, PuzzleError> {
+    const NORTH: (isize, isize) = (0, -1);
+    const SOUTH: (isize, isize) = (0, 1);
+    const EAST: (isize, isize) = (1, 0);
+    const WEST: (isize, isize) = (-1, 0);
     use Pipe::*;
-    let ret = match grid[y as usize][x as usize] {
-        Start => Some((Vec::from_iter(visited.iter().cloned()), 1)),
-        Vertical => choose(walk(x, y + 1, grid, visited), walk(x, y - 1, grid, visited)),
-        Horizontal => choose(walk(x + 1, y, grid, visited), walk(x - 1, y, grid, visited)),
-        NorthEast => choose(walk(x, y - 1, grid, visited), walk(x + 1, y, grid, visited)),
-        NorthWest => choose(walk(x, y - 1, grid, visited), walk(x - 1, y, grid, visited)),
-        SouthWest => choose(walk(x, y + 1, grid, visited), walk(x - 1, y, grid, visited)),
-        SouthEast => choose(walk(x, y + 1, grid, visited), walk(x + 1, y, grid, visited)),
-        NoPipe => None,
+    let mut path = vec![start];
+    let mut visited = HashSet::from([start]);
+    let (mut previous, mut point) = (start, first);
+    while point != start {
+        if !visited.insert(point) {
+            return Ok(None);
+        }
+        path.push(point);
+        let exits = match grid[point] {
+            Vertical => [SOUTH, NORTH],
+            Horizontal => [EAST, WEST],
+            NorthEast => [NORTH, EAST],
+            NorthWest => [NORTH, WEST],
+            SouthWest => [SOUTH, WEST],
+            SouthEast => [SOUTH, EAST],
+            Start => return Err(second_start(point)),
+            Ground => return Ok(None),
+        }
+        .map(|step| grid.step(point, step));
+        // A pipe that does not lead back where we came from breaks the loop.
+        let next = match exits {
+            [Some(a), Some(b)] if a == previous => b,
+            [Some(a), Some(b)] if b == previous => a,
+            _ => return Ok(None),
+        };
+        (previous, point) = (point, next);
     }
-    .map(|(v, e)| (v, e + 1));
-    visited.remove(&(x, y));
-    ret
+    let length = path.len() as u64;
+    Ok(Some((path, length)))
 }
 
-fn connect(&Cell { x, y, pipe }: &Cell, sx: isize, sy: isize) -> bool {
+/// Whether `pipe` at `(x, y)` could lead back to the start tile next to it.
+fn connect(pipe: Pipe, (x, y): Point, (sx, sy): Point) -> Result<bool, PuzzleError> {
     use Pipe::*;
-    match pipe {
-        Start => panic!("illegal pipe"),
-        Vertical => y - sy == 1 || sy - y == 1,
-        Horizontal => x - sx == 1 || sx - x == 1,
-        NorthEast => sx - x == 1 || y - sy == 1,
-        NorthWest => x - sx == 1 || y - sy == 1,
-        SouthWest => x - sx == 1 || sy - y == 1,
-        SouthEast => sx - x == 1 || sy - y == 1,
-        NoPipe => false,
-    }
+    Ok(match pipe {
+        Start => return Err(second_start((x, y))),
+        Vertical => y.abs_diff(sy) == 1,
+        Horizontal => x.abs_diff(sx) == 1,
+        NorthEast => sx == x + 1 || y == sy + 1,
+        NorthWest => x == sx + 1 || y == sy + 1,
+        SouthWest => x == sx + 1 || sy == y + 1,
+        SouthEast => sx == x + 1 || sy == y + 1,
+        Ground => false,
+    })
 }
 
-fn choose<A>(a: Option<(A, u64)>, b: Option<(A, u64)>) -> Option<(A, u64)> {
-    match (a, b) {
-        (Some((v, a)), Some((v2, b))) => {
-            if a > b {
-                Some((v, a))
-            } else {
-                Some((v2, b))
-            }
-        }
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b),
-        (None, None) => None,
-    }
+pub fn parse(input: &str) -> Result<Grid<Pipe>, PuzzleError> {
+    Grid::parse(input, to_pipe)
 }
 
-fn find_start(grid: &Vec<Vec<Pipe>>) -> (usize, usize) {
-    for (y, row) in grid.iter().enumerate() {
-        for (x, tile) in row.iter().enumerate() {
-            if &Pipe::Start == tile {
-                return (x, y);
-            }
-        }
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, PuzzleError> {
+    part2(&parse(input)?)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
     }
-    panic!("No start tile found");
 }
 
-fn parse_grid(file_name: &str) -> Vec<Vec<Pipe>> {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| line.chars().map(to_pipe).collect())
-        .collect()
+impl Parsed for Grid<Pipe> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2(self)?.into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    const EXAMPLE2: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const EXAMPLE3: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+
+    const EXAMPLE4: &str = "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const EXAMPLE5: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const EXAMPLE6: &str = "\
+..........
+.S------7.
+.|F----7|.
+.||OOOO||.
+.||OOOO||.
+.|L-7F-J|.
+.|II||II|.
+.L--JL--J.
+..........";
+
+    const EXAMPLE7: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const EXAMPLE8: &str = "\
+OF----7F7F7F7F-7OOOO
+O|F--7||||||||FJOOOO
+O||OFJ||||||||L7OOOO
+FJL7L7LJLJ||LJIL-7OO
+L--JOL7IIILJS7F-7L7O
+OOOOF-JIIF7FJ|L7L7L7
+OOOOL7IF7||L7|IL7L7|
+OOOOO|FJLJ|FJ|F7|OLJ
+OOOOFJL-7O||O||||OOO
+OOOOL---JOLJOLJLJOOO";
+
+    const EXAMPLE9: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    const EXAMPLE10: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJIF7FJ-
+L---JF-JLJIIIIFJLJJ7
+|F|F-JF---7IIIL7L|7|
+|FFJF7L7F-JF7IIL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
     #[test]
     fn part1_example1() {
-        assert_eq!(super::part1("src/day10_test_input.txt"), 4)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 4)
     }
     #[test]
     fn part1_example2() {
-        assert_eq!(super::part1("src/day10_test_input2.txt"), 4)
+        assert_eq!(super::solve_part1(EXAMPLE2).unwrap(), 4)
     }
     #[test]
     fn part1_example3() {
-        assert_eq!(super::part1("src/day10_test_input3.txt"), 8)
+        assert_eq!(super::solve_part1(EXAMPLE3).unwrap(), 8)
     }
     #[test]
     fn part1_example4() {
-        assert_eq!(super::part1("src/day10_test_input4.txt"), 8)
-    }
-    #[test] // run with RUST_MIN_STACK=40000000
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day10_input.txt"), 6754)
+        assert_eq!(super::solve_part1(EXAMPLE4).unwrap(), 8)
     }
     #[test]
     fn part2_example1() {
-        assert_eq!(super::part2("src/day10_test_input5.txt"), 4)
+        assert_eq!(super::solve_part2(EXAMPLE5).unwrap(), 4)
     }
     #[test]
     fn part2_example2() {
-        assert_eq!(super::part2("src/day10_test_input6.txt"), 4)
+        assert_eq!(super::solve_part2(EXAMPLE6).unwrap(), 4)
     }
     #[test]
     fn part2_example3() {
-        assert_eq!(super::part2("src/day10_test_input7.txt"), 8)
+        assert_eq!(super::solve_part2(EXAMPLE7).unwrap(), 8)
     }
     #[test]
     fn part2_example4() {
-        assert_eq!(super::part2("src/day10_test_input8.txt"), 8)
+        assert_eq!(super::solve_part2(EXAMPLE8).unwrap(), 8)
     }
     #[test]
     fn part2_example5() {
-        assert_eq!(super::part2("src/day10_test_input9.txt"), 10)
+        assert_eq!(super::solve_part2(EXAMPLE9).unwrap(), 10)
     }
     #[test]
     fn part2_example6() {
-        assert_eq!(super::part2("src/day10_test_input10.txt"), 10)
+        assert_eq!(super::solve_part2(EXAMPLE10).unwrap(), 10)
     }
-    #[test] // run with RUST_MIN_STACK=40000000
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day10_input.txt"), 6754)
+    #[test]
+    fn second_start() {
+        let error = |input| super::solve_part1(input).unwrap_err().to_string();
+        assert_eq!(
+            error(".....\n.SS7.\n.|.|.\n.L-J."),
+            "<input>: a second start tile on line 2, column 3"
+        );
+        assert_eq!(
+            error(".....\n.S-7.\n.|.|.\n.L-S."),
+            "<input>: a second start tile on line 4, column 4"
+        );
     }
 }
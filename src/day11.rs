@@ -89,8 +89,6 @@ In this example, after expanding the universe, the sum of the shortest path betw
 
 Expand the universe, then find the length of the shortest path between every pair of galaxies. What is the sum of these lengths?
 
-Your puzzle answer was 9545480.
-
 --- Part Two ---
 The galaxies are much older (and thus much farther apart) than the researcher initially estimated.
 
@@ -99,28 +97,28 @@ Now, instead of the expansion you did before, make each empty row or column one
 (In the example above, if each empty row or column were merely 10 times larger, the sum of the shortest paths between every pair of galaxies would be 1030. If each empty row or column were merely 100 times larger, the sum of the shortest paths between every pair of galaxies would be 8410. However, your universe will need to expand far beyond these values.)
 
 Starting with the same initial image, expand the universe according to these new rules, then find the length of the shortest path between every pair of galaxies. What is the sum of these lengths?
-
-Your puzzle answer was 406725732046.
 */
 
 use std::collections::HashMap;
-use std::fs::read_to_string;
+
+use crate::error::PuzzleError;
+use crate::grid::{Grid, Point};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Has {
+pub enum Has {
     Galaxy,
     NoGalaxy,
 }
 
-pub fn part1(file_name: &str, multiplicator: isize) -> isize {
-    let grid = parse_grid(file_name);
-    let er = count_expandable(&grid);
-    let transposed = transpose(&grid);
-    let ec = count_expandable(&transposed);
-    let galaxies = find_galaxies(&grid);
+pub fn part1(grid: &Grid<Has>, multiplicator: u32) -> Result<isize, PuzzleError> {
+    let multiplicator = multiplicator as isize;
+    let er = count_expandable(grid);
+    let ec = count_expandable(&grid.transpose());
+    let galaxies: Vec<Point> = grid.find_all(|&has| has == Has::Galaxy).collect();
     let mut lengths = Vec::new();
-    for (srow, scol) in &galaxies {
-        for (drow, dcol) in &galaxies {
+    for (scol, srow) in &galaxies {
+        for (dcol, drow) in &galaxies {
             let srow = *srow as isize;
             let scol = *scol as isize;
             let drow = *drow as isize;
@@ -138,20 +136,29 @@ pub fn part1(file_name: &str, multiplicator: isize) -> isize {
             let er = er[&grow] - er[&lrow];
             let ec = ec[&gcol] - ec[&lcol];
 
-            lengths.push(
-                (grow - lrow - er) + (gcol - lcol - ec) + multiplicator * er + multiplicator * ec,
-            )
+            let length = multiplicator
+                .checked_mul(er + ec)
+                .and_then(|expanded| expanded.checked_add((grow - lrow - er) + (gcol - lcol - ec)))
+                .ok_or_else(too_far)?;
+            lengths.push(length)
         }
     }
-    let s: isize = lengths.iter().sum();
-    s / 2isize
+    let s = lengths
+        .iter()
+        .try_fold(0isize, |s, &length| s.checked_add(length))
+        .ok_or_else(too_far)?;
+    Ok(s / 2isize)
+}
+
+fn too_far() -> PuzzleError {
+    PuzzleError::invalid("the distances do not fit in 64 bits")
 }
 
-fn count_expandable(grid: &Vec<Vec<Has>>) -> HashMap<isize, isize> {
+fn count_expandable(grid: &Grid<Has>) -> HashMap<isize, isize> {
     let mut curr_count = 0;
     let mut counts = HashMap::new();
-    for row in 0..grid.len() {
-        if grid[row].contains(&Has::Galaxy) {
+    for (row, cells) in grid.rows().enumerate() {
+        if cells.contains(&Has::Galaxy) {
             counts.insert(row as isize, curr_count);
         } else {
             curr_count += 1;
@@ -160,66 +167,116 @@ fn count_expandable(grid: &Vec<Vec<Has>>) -> HashMap<isize, isize> {
     counts
 }
 
-fn transpose(grid: &Vec<Vec<Has>>) -> Vec<Vec<Has>> {
-    let mut new_grid = Vec::new();
-    for i in 0..grid[0].len() {
-        let mut row = Vec::new();
-        for j in 0..grid.len() {
-            row.push(grid[j][i]);
-        }
-        new_grid.push(row);
+fn to_has(c: char) -> Option<Has> {
+    match c {
+        '.' => Some(Has::NoGalaxy),
+        '#' => Some(Has::Galaxy),
+        _ => None,
     }
-    new_grid
 }
 
-fn to_has(c: char) -> Has {
-    match c {
-        '.' => Has::NoGalaxy,
-        '#' => Has::Galaxy,
-        _ => panic!("illegal cell"),
-    }
+pub fn parse(input: &str) -> Result<Grid<Has>, PuzzleError> {
+    Grid::parse(input, to_has)
 }
 
-fn find_galaxies(grid: &Vec<Vec<Has>>) -> Vec<(usize, usize)> {
-    let mut galaxies = Vec::new();
-    for (x, row) in grid.iter().enumerate() {
-        for (y, tile) in row.iter().enumerate() {
-            if &Has::Galaxy == tile {
-                galaxies.push((x, y));
-            }
-        }
+pub fn solve_part1(input: &str) -> Result<isize, PuzzleError> {
+    part1(&parse(input)?, 2)
+}
+
+pub fn solve_part2(input: &str) -> Result<isize, PuzzleError> {
+    part1(&parse(input)?, 1_000_000)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        &["multiplicator"]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
     }
-    galaxies
 }
 
-fn parse_grid(file_name: &str) -> Vec<Vec<Has>> {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| line.chars().map(to_has).collect())
-        .collect()
+impl Parsed for Grid<Has> {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, PuzzleError> {
+        let multiplicator = match part {
+            Part::One => 2,
+            Part::Two => 1_000_000,
+        };
+        let multiplicator = params.get_or("multiplicator", multiplicator)?;
+        if multiplicator == 0 {
+            return Err(PuzzleError::Param {
+                name: "multiplicator".to_string(),
+                value: multiplicator.to_string(),
+                reason: "an empty row grows into at least one row".to_string(),
+            });
+        }
+        Ok(part1(self, multiplicator)?.into())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::solution::{Answer, Params, Parsed, Part};
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day11_test_input.txt", 2), 374)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day11_input.txt", 2), 9545480)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 374)
     }
     #[test]
     fn part2_example1() {
-        assert_eq!(super::part1("src/day11_test_input.txt", 10), 1030)
+        assert_eq!(
+            super::part1(&super::parse(EXAMPLE).unwrap(), 10).unwrap(),
+            1030
+        )
     }
     #[test]
     fn part2_example2() {
-        assert_eq!(super::part1("src/day11_test_input.txt", 100), 8410)
+        assert_eq!(
+            super::part1(&super::parse(EXAMPLE).unwrap(), 100).unwrap(),
+            8410
+        )
     }
     #[test]
-    fn part2_actual() {
-        assert_eq!(super::part1("src/day11_input.txt", 1000000), 406725732046)
+    fn multiplicator() {
+        let grid = super::parse(EXAMPLE).unwrap();
+        let solve = |value| {
+            let mut params = Params::default();
+            params.set("multiplicator", value);
+            grid.solve(Part::Two, &params).map_err(|e| e.to_string())
+        };
+        assert_eq!(solve("10"), Ok(Answer(1030)));
+        assert_eq!(
+            solve("-1"),
+            Err(
+                "invalid value `-1` for `multiplicator`: invalid digit found in string".to_string()
+            )
+        );
+        assert_eq!(
+            solve("0"),
+            Err(
+                "invalid value `0` for `multiplicator`: an empty row grows into at least one row"
+                    .to_string()
+            )
+        );
+        assert!(solve("99999999999").is_err());
     }
 }
@@ -70,139 +70,170 @@ Adding all of the possible arrangement counts together produces a total of 21 ar
 For each row, count all of the different arrangements of operational and broken springs that meet the given criteria. What is the sum of those counts?
 */
 
-use std::fs::read_to_string;
 use std::fmt::Debug;
 
+use nom::character::complete::{char, one_of, space1, u64};
+use nom::combinator::map;
+use nom::multi::{many1, separated_list1};
+use nom::sequence::separated_pair;
+
+use crate::error::PuzzleError;
+use crate::log::{debug, trace};
+use crate::parsing::parse_lines;
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
 #[derive(PartialEq, Eq, Clone, Copy)]
-enum Condition {
+pub enum Condition {
     Damaged,
     Operational,
     Unknown,
 }
 
 impl Debug for Condition {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    match self {
-      Condition::Damaged => write!(f, "#"),
-      Condition::Operational => write!(f, "."),
-      Condition::Unknown => write!(f, "?"),
-    }  
-  }
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Damaged => write!(f, "#"),
+            Condition::Operational => write!(f, "."),
+            Condition::Unknown => write!(f, "?"),
+        }
+    }
 }
 
-pub fn part1(file_name: &str) -> usize {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(parse_row)
+pub fn part1(rows: &[Row]) -> u64 {
+    rows.iter()
         .map(|(conditions, counts)| {
-            let count = count_arrangements(&conditions, &counts, 0, counts.iter().sum(), 0);
-            println!("answer {conditions:?} {counts:?} {count}");
+            let count = count_arrangements(conditions, counts);
+            debug!("answer {conditions:?} {counts:?} {count}");
             count
         })
         .sum()
 }
 
-fn count_arrangements(
-    conditions: &[Condition],
-    counts: &[usize],
-    icount: usize,
-    total_damaged: usize,
-    used_damaged: usize,
-) -> usize {
-    println!("enter {conditions:?} {counts:?} {icount} {total_damaged} {used_damaged}");
-    if icount < counts.len() && total_damaged <= used_damaged {
-        return 0;
-    }
-    if icount == counts.len() {
-        if conditions.is_empty() || total_damaged == used_damaged {
-            return 1;
-        } else {
-            return 0;
-        }
+pub fn part2(rows: &[Row]) -> u64 {
+    let unfolded: Vec<Row> = rows.iter().map(unfold).collect();
+    part1(&unfolded)
+}
+
+/// The row five times over, the copies of its conditions joined by an
+/// unknown spring.
+fn unfold((conditions, counts): &Row) -> Row {
+    let mut unfolded = conditions.clone();
+    for _ in 1..5 {
+        unfolded.push(Condition::Unknown);
+        unfolded.extend_from_slice(conditions);
     }
-    let mut arrangements = 0;
-    let count = counts[icount];
-    for icon in 0..conditions.len() {
-        if icon + count < conditions.len()
-            && conditions[icon + count] != Condition::Damaged
-            && (icon == 0 || conditions[icon - 1] != Condition::Damaged)
-        {
-            if conditions[icon..icon + count]
-                .iter()
-                .all(|&c| c != Condition::Operational)
-            {
-                println!(
-                    "recursing conditions:{conditions:?} counts:{counts:?} icount:{icount} count:{count} icon:{icon} arrangements:{arrangements}"
-                );
-                arrangements += count_arrangements(
-                    &conditions[icon + count + 1..conditions.len()],
-                    counts,
-                    icount + 1,
-                    total_damaged,
-                    used_damaged
-                        + count
-                        + conditions[0..icon]
-                            .iter()
-                            .filter(|&&c| c == Condition::Damaged)
-                            .count(),
-                );
-                println!(
-                    "recursed conditions:{conditions:?} counts:{counts:?} icount:{icount} count:{count} icon:{icon} arrangements:{arrangements}"
-                );
+    (unfolded, counts.repeat(5))
+}
+
+/// The ways to place groups of damaged springs of the sizes in `counts`, in
+/// order, among `conditions`. Worked back from the end of the row:
+/// `ways[i][j]` counts the arrangements of `counts[j..]` in `conditions[i..]`.
+fn count_arrangements(conditions: &[Condition], counts: &[usize]) -> u64 {
+    let (n, m) = (conditions.len(), counts.len());
+    let mut ways = vec![vec![0u64; m + 1]; n + 1];
+    ways[n][m] = 1;
+    for i in (0..n).rev() {
+        for j in 0..=m {
+            let mut here = 0;
+            if conditions[i] != Condition::Damaged {
+                here += ways[i + 1][j];
             }
-        }
-        if icon + count == conditions.len()
-            && (icon == 0 || conditions[icon - 1] != Condition::Damaged)
-        {
-            if conditions[icon..icon + count]
-                .iter()
-                .all(|&c| c != Condition::Operational)
-            {
-                arrangements += if icount == counts.len() - 1 { 1 } else { 0 };
-                println!(
-                    "terminate conditions:{conditions:?} counts:{counts:?} icount:{icount} count:{count} icon:{icon} arrangements:{arrangements}"
-                );
+            // A group starting at `i` must fit before the end of the row or
+            // the next spring not damaged.
+            if let Some(&count) = counts.get(j) {
+                let end = i + count;
+                if conditions[i] != Condition::Operational
+                    && end <= n
+                    && !conditions[i..end].contains(&Condition::Operational)
+                    && conditions.get(end) != Some(&Condition::Damaged)
+                {
+                    here += ways[(end + 1).min(n)][j + 1];
+                }
             }
+            trace!("{i} {j} {here}");
+            ways[i][j] = here;
         }
     }
-
-    arrangements
+    ways[0][0]
 }
 
-fn to_condition(c: char) -> Condition {
-    match c {
+/// A row of spring conditions and the sizes of its damaged groups.
+pub type Row = (Vec<Condition>, Vec<usize>);
+
+pub fn parse(input: &str) -> Result<Vec<Row>, PuzzleError> {
+    let condition = map(one_of(".#?"), |c| match c {
         '.' => Condition::Operational,
         '#' => Condition::Damaged,
-        '?' => Condition::Unknown,
-        _ => panic!("illegal cell"),
+        _ => Condition::Unknown,
+    });
+    let count = map(u64, |n| n as usize);
+    let row = separated_pair(many1(condition), space1, separated_list1(char(','), count));
+    parse_lines(input, row)
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part1(&parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part2(&parse(input)?))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
     }
 }
 
-fn parse_row(row: &str) -> (Vec<Condition>, Vec<usize>) {
-    let mut parts = row.split_ascii_whitespace();
-    let conditions = parts.next().unwrap().chars().map(to_condition).collect();
-    let counts = parts
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect();
-    (conditions, counts)
+impl Parsed for Vec<Row> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self).into(),
+            Part::Two => part2(self).into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    const EXAMPLE2: &str = "\
+??.?#???.????. 1,1
+#?.?#???.????. 1,1";
+
     #[test]
     fn part1_example1() {
-        assert_eq!(super::part1("src/day12_test_input.txt"), 21)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 21)
     }
     #[test]
     fn part1_example2() {
-        assert_eq!(super::part1("src/day12_test_input2.txt"), 21)
+        assert_eq!(super::solve_part1(EXAMPLE2).unwrap(), 9)
+    }
+    #[test]
+    fn part2_example() {
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 525152)
     }
     #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day12_input.txt"), 21)
+    fn unfold() {
+        let rows = super::parse(".# 1").unwrap();
+        let (conditions, counts) = super::unfold(&rows[0]);
+        assert_eq!(
+            format!("{conditions:?}"),
+            "[., #, ?, ., #, ?, ., #, ?, ., #, ?, ., #]"
+        );
+        assert_eq!(counts, [1; 5]);
     }
 }
@@ -25,8 +25,6 @@ In the example above, games 1, 2, and 5 would have been possible if the bag had
 
 Determine which games would have been possible if the bag had been loaded with only 12 red cubes, 13 green cubes, and 14 blue cubes. What is the sum of the IDs of those games?
 
-Your puzzle answer was 2541.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -50,115 +48,349 @@ The power of a set of cubes is equal to the numbers of red, green, and blue cube
 
 For each game, find the minimum set of cubes that must have been present. What is the sum of the power of these sets?
 
-Your puzzle answer was 66016.
-
 */
 
-use std::fs::read_to_string;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
-use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::char;
+use nom::character::complete::{alpha1, char};
 use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
+use nom::sequence::{pair, separated_pair};
 use nom::IResult;
 
+use crate::error::PuzzleError;
+use crate::log::trace;
+use crate::parsing::{labeled_id, parse_lines};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
 #[derive(Debug)]
-struct Game {
-    id: u64,
-    sets: Vec<Cubes>,
+pub struct Game<'a> {
+    pub id: u64,
+    pub sets: Vec<Cubes<'a>>,
 }
 
-#[derive(Debug)]
-struct Cubes {
-    red: u64,
-    green: u64,
-    blue: u64,
+/// The cubes of one handful, by colour. Any colour name goes; a colour named
+/// twice in a handful counts the sum.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Cubes<'a>(pub BTreeMap<&'a str, u64>);
+
+impl Cubes<'_> {
+    pub fn count(&self, colour: &str) -> u64 {
+        self.0.get(colour).copied().unwrap_or_default()
+    }
 }
 
-impl Game {
-    fn possible(&self) -> bool {
-        self.sets
-            .iter()
-            .all(|s| s.red <= 12 && s.green <= 13 && s.blue <= 14)
+/// How many cubes of each colour the bag holds, as the `bag` parameter sets
+/// it: `red=12,green=13,blue=14`. Colours the bag does not list, it holds
+/// none of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag(pub BTreeMap<String, u64>);
+
+impl Bag {
+    pub fn count(&self, colour: &str) -> u64 {
+        self.0.get(colour).copied().unwrap_or_default()
+    }
+
+    /// This bag with `cubes` fewer cubes of `colour`, or none left.
+    pub fn without(&self, colour: &str, cubes: u64) -> Bag {
+        let mut bag = self.clone();
+        if let Some(count) = bag.0.get_mut(colour) {
+            *count = count.saturating_sub(cubes);
+        }
+        bag
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u64)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u64)>>(iter: I) -> Self {
+        Bag(iter
+            .into_iter()
+            .map(|(colour, count)| (colour.to_string(), count))
+            .collect())
+    }
+}
+
+/// The bag of the puzzle: 12 red cubes, 13 green cubes and 14 blue cubes.
+impl Default for Bag {
+    fn default() -> Self {
+        [("red", 12), ("green", 13), ("blue", 14)]
+            .into_iter()
+            .collect()
+    }
+}
+
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bag = BTreeMap::new();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (colour, count) = item
+                .split_once('=')
+                .and_then(|(colour, count)| Some((colour.trim(), count.trim().parse().ok()?)))
+                .ok_or_else(|| format!("`{item}` is not colour=count"))?;
+            if bag.insert(colour.to_string(), count).is_some() {
+                return Err(format!("`{colour}` is given twice"));
+            }
+        }
+        Ok(Bag(bag))
+    }
+}
+
+impl<'a> Game<'a> {
+    fn possible(&self, bag: &Bag) -> bool {
+        self.over(bag).is_none()
+    }
+
+    /// The first draw that needs more of its colour than `bag` holds.
+    fn over(&self, bag: &Bag) -> Option<Draw<'a>> {
+        self.draws()
+            .find(|draw| draw.count > bag.count(draw.colour))
+    }
+
+    fn draws(&self) -> impl Iterator<Item = Draw<'a>> + '_ {
+        self.sets.iter().enumerate().flat_map(move |(set, cubes)| {
+            cubes.0.iter().map(move |(&colour, &count)| Draw {
+                game: self.id,
+                set,
+                colour,
+                count,
+            })
+        })
+    }
+
+    /// The most cubes of each colour any one handful shows, drawn first by
+    /// the returned handful: the fewest cubes that make the game possible.
+    pub fn fewest(&self) -> BTreeMap<&'a str, Draw<'a>> {
+        most(self.draws())
     }
 
     fn power(&self) -> u64 {
-        self.sets.iter().map(|s| s.red).max().unwrap_or_default()
-            * self.sets.iter().map(|s| s.green).max().unwrap_or_default()
-            * self.sets.iter().map(|s| s.blue).max().unwrap_or_default()
+        self.fewest().values().map(|draw| draw.count).product()
+    }
+}
+
+/// Some cubes of one colour, shown in handful `set` of game `game`, counting
+/// handfuls from 0 in the order of [`Game::sets`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Draw<'a> {
+    pub game: u64,
+    pub set: usize,
+    pub colour: &'a str,
+    pub count: u64,
+}
+
+impl fmt::Display for Draw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "game {} handful {}: {} {}",
+            self.game,
+            self.set + 1,
+            self.count,
+            self.colour
+        )
+    }
+}
+
+/// The largest draw of each colour, the first one where several tie.
+fn most<'a>(draws: impl Iterator<Item = Draw<'a>>) -> BTreeMap<&'a str, Draw<'a>> {
+    let mut most = BTreeMap::new();
+    for draw in draws {
+        most.entry(draw.colour)
+            .and_modify(|most: &mut Draw| {
+                if draw.count > most.count {
+                    *most = draw;
+                }
+            })
+            .or_insert(draw);
     }
+    most
+}
+
+/// The smallest bag every game is possible with, as the draw that needs
+/// each colour's count.
+pub fn minimal_bag<'a>(games: &[Game<'a>]) -> BTreeMap<&'a str, Draw<'a>> {
+    most(games.iter().flat_map(Game::draws))
+}
+
+/// The games possible with `bag` but not with `smaller`, each as the first
+/// draw `smaller` cannot hold; with [`Bag::without`], the games that taking
+/// cubes out of the bag rules out.
+pub fn ruled_out<'a>(games: &[Game<'a>], bag: &Bag, smaller: &Bag) -> Vec<Draw<'a>> {
+    games
+        .iter()
+        .filter(|game| game.possible(bag))
+        .filter_map(|game| game.over(smaller))
+        .collect()
 }
 
-pub fn part1(file_name: &str) -> u64 {
-    let games: Vec<_> = read_to_string(file_name)
-        .unwrap()
-        .split('\n')
-        .map(parse_game)
-        .collect();
+/// For each game possible with `bag`, the draw that comes closest to the
+/// bag's limit for its colour, the first colour by name where several come
+/// as close. When it reaches the limit, that limit binds: one cube fewer of
+/// that colour rules the game out.
+pub fn binding<'a>(games: &[Game<'a>], bag: &Bag) -> Vec<Draw<'a>> {
     games
         .iter()
-        .map(|g| if g.possible() { g.id } else { 0 })
+        .filter(|game| game.possible(bag))
+        .filter_map(|game| {
+            game.fewest()
+                .into_values()
+                .min_by_key(|draw| bag.count(draw.colour) - draw.count)
+        })
+        .collect()
+}
+
+pub fn part1(games: &[Game]) -> u64 {
+    part1_with(games, &Bag::default())
+}
+
+/// Part 1, with the cubes in `bag` rather than the puzzle's.
+pub fn part1_with(games: &[Game], bag: &Bag) -> u64 {
+    games
+        .iter()
+        .map(|g| if g.possible(bag) { g.id } else { 0 })
         .sum()
 }
 
-pub fn part2(file_name: &str) -> u64 {
-    let games: Vec<_> = read_to_string(file_name)
-        .unwrap()
-        .split('\n')
-        .map(parse_game)
-        .collect();
+pub fn part2(games: &[Game]) -> u64 {
     games.iter().map(|g| g.power()).sum()
 }
 
-fn parse_game(input: &str) -> Game {
-    let (_, ((_, id), sets)) = separated_pair(
-        separated_pair(tag("Game"), char(' '), nom::character::complete::u64),
-        tag(": "),
-        separated_list0(tag("; "), parse_cubes),
-    )(input)
-    .unwrap();
-    eprintln!("{}: {:?}", id, sets);
-    Game { id, sets }
+pub fn parse(input: &str) -> Result<Vec<Game<'_>>, PuzzleError> {
+    parse_lines(input, parse_game)
+}
+
+fn parse_game(input: &str) -> IResult<&str, Game<'_>> {
+    let (input, (id, sets)) =
+        pair(labeled_id("Game"), separated_list0(tag("; "), parse_cubes))(input)?;
+    trace!("{}: {:?}", id, sets);
+    Ok((input, Game { id, sets }))
 }
 
-fn parse_cubes(input: &str) -> IResult<&str, Cubes> {
-    let (input, counts): (_, Vec<_>) = separated_list0(
+fn parse_cubes(input: &str) -> IResult<&str, Cubes<'_>> {
+    let (input, counts) = separated_list0(
         tag(", "),
-        separated_pair(
-            nom::character::complete::u64,
-            char(' '),
-            alt((tag("red"), tag("green"), tag("blue"))),
-        ),
+        separated_pair(nom::character::complete::u64, char(' '), alpha1),
     )(input)?;
 
-    Ok((
-        input,
-        Cubes {
-            red: count(&counts, "red"),
-            green: count(&counts, "green"),
-            blue: count(&counts, "blue"),
-        },
-    ))
+    let mut cubes = Cubes::default();
+    for (count, colour) in counts {
+        *cubes.0.entry(colour).or_default() += count;
+    }
+    Ok((input, cubes))
 }
 
-fn count(counts: &Vec<(u64, &str)>, color: &str) -> u64 {
-    counts
-        .iter()
-        .map(|(count, c)| if *c == color { *count } else { 0 })
-        .sum()
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part1(&parse(input)?))
 }
 
-#[cfg(test)]
-mod test {
-#[test]
-fn part1() {
-    assert_eq!(super::part1("src/day2_test_input.txt"), 8);
-    assert_eq!(super::part1("src/day2_input.txt"), 2541)
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part2(&parse(input)?))
 }
-#[test]
-fn part2() {
-    assert_eq!(super::part2("src/day2_test_input.txt"), 2286);
-    assert_eq!(super::part2("src/day2_input.txt"), 66016)
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        &["bag"]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
 }
+
+impl Parsed for Vec<Game<'_>> {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1_with(self, &params.get_or("bag", Bag::default())?).into(),
+            Part::Two => part2(self).into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn part1() {
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 8);
+    }
+    #[test]
+    fn part2() {
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 2286);
+    }
+
+    #[test]
+    fn any_colours() {
+        let games = super::parse(
+            "Game 1: 3 purple, 1 red; 2 purple, 2 purple\nGame 2: 1 red, 1 teal; 2 green\n",
+        )
+        .unwrap();
+        assert_eq!(games[0].sets[1].count("purple"), 4);
+        assert_eq!(super::part1(&games), 0);
+        let bag: super::Bag = "red=12, green=13, blue=14, purple=3".parse().unwrap();
+        assert_eq!(super::part1_with(&games, &bag), 0);
+        let bag: super::Bag = "red=1,purple=4,teal=1,green=2".parse().unwrap();
+        assert_eq!(super::part1_with(&games, &bag), 3);
+        assert_eq!(super::part2(&games), 4 + 2);
+
+        assert!("red=12,red=1".parse::<super::Bag>().is_err());
+        assert!("red:12".parse::<super::Bag>().is_err());
+    }
+
+    #[test]
+    fn inference() {
+        let games = super::parse(EXAMPLE).unwrap();
+        let minimal = super::minimal_bag(&games);
+        assert_eq!(
+            minimal.values().map(|d| d.to_string()).collect::<Vec<_>>(),
+            [
+                "game 4 handful 3: 15 blue",
+                "game 3 handful 2: 13 green",
+                "game 3 handful 1: 20 red"
+            ]
+        );
+        let bag: super::Bag = minimal.values().map(|d| (d.colour, d.count)).collect();
+        assert_eq!(super::part1_with(&games, &bag), 15);
+
+        let ruled_out = super::ruled_out(&games, &bag, &bag.without("blue", 1));
+        assert_eq!(
+            ruled_out
+                .iter()
+                .map(|d| (d.game, d.set))
+                .collect::<Vec<_>>(),
+            [(4, 2)]
+        );
+        let puzzle = super::Bag::default();
+        let ruled_out = super::ruled_out(&games, &puzzle, &puzzle.without("blue", 9));
+        assert_eq!(ruled_out.len(), 1);
+        assert_eq!(ruled_out[0].to_string(), "game 1 handful 2: 6 blue");
+        assert!(super::ruled_out(&games, &puzzle, &puzzle.without("red", 1)).is_empty());
+
+        let binding = super::binding(&games, &bag);
+        assert_eq!(
+            binding.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+            [
+                "game 1 handful 2: 6 blue",
+                "game 2 handful 2: 3 green",
+                "game 3 handful 2: 13 green",
+                "game 4 handful 3: 15 blue",
+                "game 5 handful 1: 3 green"
+            ]
+        );
+    }
 }
@@ -28,8 +28,6 @@ In this schematic, two numbers are not part numbers because they are not adjacen
 
 Of course, the actual engine schematic is much larger. What is the sum of all of the part numbers in the engine schematic?
 
-Your puzzle answer was 538046.
-
 --- Part Two ---
 The engineer finds the missing part and installs it in the engine! As the engine springs to life, you jump in the closest gondola, finally ready to ascend to the water source.
 
@@ -56,156 +54,151 @@ Consider the same engine schematic again:
 In this schematic, there are two gears. The first is in the top left; it has part numbers 467 and 35, so its gear ratio is 16345. The second gear is in the lower right; its gear ratio is 451490. (The * adjacent to 617 is not a gear because it is only adjacent to one part number.) Adding up all of the gear ratios produces 467835.
 
 What is the sum of all of the gear ratios in your engine schematic?
-
-Your puzzle answer was 81709807.
 */
 
-use std::fs::read_to_string;
-
-use nom::branch::alt;
-use nom::character::complete::{anychar, char};
-use nom::combinator::map;
-use nom::error::Error;
+use crate::error::PuzzleError;
+use crate::grid::{Grid, Point};
+use crate::log::trace;
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
+/// A part number candidate, running from `start` to `end` inclusive along row
+/// `y`.
 #[derive(Debug)]
 struct Number {
     value: u64,
+    y: usize,
     start: usize,
     end: usize,
 }
 
-#[derive(Debug)]
-struct Symbol {
-    index: usize,
-    value: char,
+impl Number {
+    fn adjacent(&self, (x, y): Point) -> bool {
+        self.y.abs_diff(y) <= 1 && x + 1 >= self.start && x <= self.end + 1
+    }
 }
 
-impl Number {
-    fn part(&self, symbols: &Vec<Symbol>, width: usize) -> bool {
-        symbols.iter().any(|s| {
-            let yes = (s.index >= self.start - 1 && s.index <= self.end + 1)
-                || (s.index - width >= self.start - 1 && s.index - width <= self.end + 1)
-                || (s.index + width >= self.start - 1 && s.index + width <= self.end + 1);
-            if yes {
-                eprintln!("{:?}, {:?}", s, self);
+fn is_symbol(c: &char) -> bool {
+    *c != '.' && !c.is_ascii_digit()
+}
+
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
+}
+
+pub fn parse(input: &str) -> Result<Schematic, PuzzleError> {
+    let grid = Grid::parse(input, Some)?;
+    let mut numbers = Vec::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let digits = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 {
+                x += 1;
+                continue;
             }
-            yes
-        })
+            let value = row[x..x + digits]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .map_err(|_| PuzzleError::invalid(format!("number too large on line {}", y + 1)))?;
+            numbers.push(Number {
+                value,
+                y,
+                start: x,
+                end: x + digits - 1,
+            });
+            x += digits;
+        }
     }
+    Ok(Schematic { grid, numbers })
 }
 
-pub fn part1(file_name: &str) -> u64 {
-    let input = read_to_string(file_name).unwrap();
-    let width = input.lines().next().unwrap().len();
-    let (numbers, symbols) = parse_schematic(&input);
-    numbers
+pub fn part1(schematic: &Schematic) -> u64 {
+    let symbols: Vec<Point> = schematic.grid.find_all(is_symbol).collect();
+    schematic
+        .numbers
         .iter()
-        .filter(|n| n.part(&symbols, width))
+        .filter(|n| {
+            let symbol = symbols.iter().find(|&&s| n.adjacent(s));
+            if let Some(symbol) = symbol {
+                trace!("{n:?} next to {symbol:?}");
+            }
+            symbol.is_some()
+        })
         .map(|n| n.value)
         .sum()
 }
 
-impl Symbol {
-    fn adjacent_parts(&self, numbers: &Vec<Number>, width: usize) -> Vec<u64> {
-        numbers
-            .iter()
-            .filter_map(|n| {
-                let yes = (self.index >= n.start - 1 && self.index <= n.end + 1)
-                    || (self.index - width >= n.start - 1 && self.index - width <= n.end + 1)
-                    || (self.index + width >= n.start - 1 && self.index + width <= n.end + 1);
-                if yes {
-                    eprintln!("{:?}, {:?}", n, self);
-                    Some(n.value)
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
-}
-
-pub fn part2(file_name: &str) -> u64 {
-    let input = read_to_string(file_name).unwrap();
-    let width = input.lines().next().unwrap().len();
-    let (numbers, symbols) = parse_schematic(&input);
-    symbols
-        .iter()
-        .filter_map(|s| {
-            if s.value == '*' {
-                let adjacent_parts = s.adjacent_parts(&numbers, width);
-                if adjacent_parts.len() == 2 {
-                    Some(adjacent_parts[0] * adjacent_parts[1])
-                } else {
-                    None
-                }
-            } else {
-                None
+pub fn part2(schematic: &Schematic) -> u64 {
+    schematic
+        .grid
+        .find_all(|&c| c == '*')
+        .filter_map(|gear| {
+            let parts: Vec<u64> = schematic
+                .numbers
+                .iter()
+                .filter(|n| n.adjacent(gear))
+                .map(|n| n.value)
+                .collect();
+            trace!("{gear:?} next to {parts:?}");
+            match parts[..] {
+                [a, b] => Some(a * b),
+                _ => None,
             }
         })
         .sum()
 }
 
-#[derive(Debug)]
-enum Token {
-    Num(u64),
-    Sym(char),
-    Dot,
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part1(&parse(input)?))
 }
 
-fn parse_schematic(input: &str) -> (Vec<Number>, Vec<Symbol>) {
-    let input = input.replace("\n", "");
-    let mut numbers = Vec::new();
-    let mut symbols = Vec::new();
-    let mut curr = 1;
-    let mut input = input.as_str();
-    loop {
-        let orig_len = input.len();
-        let (remaining, token) = alt::<_, _, Error<_>, _>((
-            map(char('.'), |_| Token::Dot),
-            map(nom::character::complete::u64, Token::Num),
-            map(anychar, Token::Sym),
-        ))(input)
-        .unwrap();
-        //eprintln!("{}, {:?}", remaining, token);
-
-        match token {
-            Token::Num(value) => {
-                numbers.push(Number {
-                    value,
-                    start: curr,
-                    end: curr + orig_len - remaining.len() - 1,
-                });
-            }
-            Token::Sym(value) => {
-                symbols.push(Symbol { index: curr, value });
-            }
-            Token::Dot => {}
-        }
-        if remaining.is_empty() {
-            break;
-        }
-        curr += orig_len - remaining.len();
-        input = remaining;
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part2(&parse(input)?))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Schematic {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self).into(),
+            Part::Two => part2(self).into(),
+        })
     }
-    (numbers, symbols)
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day3_test_input.txt"), 4361)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day3_input.txt"), 538046)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 4361)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day3_test_input.txt"), 467835)
-    }
-    #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day3_input.txt"), 81709807)
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 467835)
     }
 }
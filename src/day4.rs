@@ -33,8 +33,6 @@ So, in this example, the Elf's pile of scratchcards is worth 13 points.
 
 Take a seat in the large pile of colorful cards. How many points are they worth in total?
 
-Your puzzle answer was 21158.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -64,27 +62,31 @@ Your one instance of card 6 (one original) has no matching numbers and wins no m
 Once all of the originals and copies have been processed, you end up with 1 instance of card 1, 2 instances of card 2, 4 instances of card 3, 8 instances of card 4, 14 instances of card 5, and 1 instance of card 6. In total, this example pile of scratchcards causes you to ultimately have 30 scratchcards!
 
 Process all of the original and copied scratchcards until no more scratchcards are won. Including the original set of scratchcards, how many total scratchcards do you end up with?
-
-Your puzzle answer was 6050769.
 */
 
-use std::fs::read_to_string;
-
-use nom::bytes::complete::tag;
 use nom::character::complete::{char, space1};
-use nom::error::Error;
-use nom::multi::separated_list0;
-use nom::sequence::{delimited, separated_pair, terminated};
+use nom::sequence::{delimited, preceded, separated_pair};
+use nom::IResult;
+
+use crate::error::PuzzleError;
+use crate::log::trace;
+use crate::parsing::{labeled_id, numbers, parse_lines};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
 #[derive(Debug)]
-struct Card {
-    winning: Vec<u64>,
-    have: Vec<u64>,
+pub struct Card {
+    pub winning: Vec<u64>,
+    pub have: Vec<u64>,
 }
 
 impl Card {
-    fn score(&self) -> u64 {
-        2u64.pow(self.matches() as u32) / 2
+    /// One point for the first match, doubled for every match after it;
+    /// `None` when that does not fit in a u64.
+    fn score(&self) -> Option<u64> {
+        match self.matches() {
+            0 => Some(0),
+            matches => 1u64.checked_shl(u32::try_from(matches - 1).ok()?),
+        }
     }
     fn matches(&self) -> usize {
         self.have
@@ -94,60 +96,121 @@ impl Card {
     }
 }
 
-pub fn part1(file_name: &str) -> u64 {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|c| parse_card_line(c).score())
-        .sum()
+fn too_many_points() -> PuzzleError {
+    PuzzleError::invalid("the points do not fit in 64 bits")
 }
 
-pub fn part2(file_name: &str) -> u64 {
-    let matcheses: Vec<_> = read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|c| parse_card_line(c).matches())
-        .collect();
-    let mut counts = vec![1; matcheses.len()];
-    for (index, matches) in matcheses.iter().enumerate() {
-        eprintln!("{} {}", index, matches);
-        for offset in 1..=*matches {
-            counts[index + offset] += counts[index];
+pub fn part1(cards: &[Card]) -> Result<u64, PuzzleError> {
+    cards.iter().try_fold(0u64, |sum, card| {
+        card.score()
+            .and_then(|score| sum.checked_add(score))
+            .ok_or_else(too_many_points)
+    })
+}
+
+pub fn part2(cards: &[Card]) -> Result<u64, PuzzleError> {
+    let matcheses: Vec<_> = cards.iter().map(Card::matches).collect();
+    let mut counts = vec![1u64; matcheses.len()];
+    for (index, &matches) in matcheses.iter().enumerate() {
+        trace!("{} {}", index, matches);
+        if index + matches >= counts.len() {
+            return Err(PuzzleError::invalid(format!(
+                "card {} wins copies of cards past the last one",
+                index + 1
+            )));
+        }
+        let copies = counts[index];
+        for count in &mut counts[index + 1..=index + matches] {
+            *count = count.checked_add(copies).ok_or_else(too_many_cards)?;
         }
     }
-    counts.iter().sum()
+    counts
+        .iter()
+        .try_fold(0u64, |sum, &count| sum.checked_add(count))
+        .ok_or_else(too_many_cards)
 }
 
-fn parse_card_line(input: &str) -> Card {
-    let (_, (_, (winning, have))) = separated_pair::<_, _, _, _, Error<_>, _, _, _>(
-        separated_pair(tag("Card"), space1, nom::character::complete::u64),
-        terminated(char(':'), space1),
-        separated_pair(
-            separated_list0(space1, nom::character::complete::u64),
-            delimited(space1, char('|'), space1),
-            separated_list0(space1, nom::character::complete::u64),
-        ),
-    )(input)
-    .unwrap();
-    Card { winning, have }
+fn too_many_cards() -> PuzzleError {
+    PuzzleError::invalid("the number of cards does not fit in 64 bits")
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>, PuzzleError> {
+    parse_lines(input, parse_card_line)
+}
+
+fn parse_card_line(input: &str) -> IResult<&str, Card> {
+    let (input, (winning, have)) = preceded(
+        labeled_id("Card"),
+        separated_pair(numbers, delimited(space1, char('|'), space1), numbers),
+    )(input)?;
+    Ok((input, Card { winning, have }))
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    part2(&parse(input)?)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Vec<Card> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2(self)?.into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day4_test_input.txt"), 13)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 13)
     }
     #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day4_input.txt"), 21158)
+    fn part2_example() {
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 30)
     }
     #[test]
-    fn part2_example() {
-        assert_eq!(super::part2("src/day4_test_input.txt"), 30)
+    fn copies_past_the_last_card() {
+        assert_eq!(
+            super::solve_part2("Card 1: 1 2 | 1 2")
+                .unwrap_err()
+                .to_string(),
+            "<input>: card 1 wins copies of cards past the last one"
+        )
     }
     #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day4_input.txt"), 6050769)
+    fn points_past_64_bits() {
+        let numbers: Vec<_> = (1..=65).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let card = format!("Card 1: {numbers} | {numbers}");
+        assert!(super::solve_part1(&card).is_err());
+        let numbers: Vec<_> = (1..=64).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let card = format!("Card 1: {numbers} | {numbers}");
+        assert_eq!(super::solve_part1(&card).unwrap(), 1 << 63);
     }
 }
@@ -94,8 +94,6 @@ So, the lowest location number in this example is 35.
 
 What is the lowest location number that corresponds to any of the initial seed numbers?
 
-Your puzzle answer was 424490994.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -111,39 +109,36 @@ Now, rather than considering four seed numbers, you need to consider a total of
 In the above example, the lowest location number can be obtained from seed number 82, which corresponds to soil 84, fertilizer 84, water 84, light 77, temperature 45, humidity 46, and location 46. So, the lowest location number is 46.
 
 Consider all of the initial seed numbers listed in the ranges on the first line of the almanac. What is the lowest location number that corresponds to any of the initial seed numbers?
-
-Your puzzle answer was 15290096.
 */
 
-use std::fs::read_to_string;
+use nom::bytes::complete::{is_not, tag};
+use nom::character::complete::{line_ending, space1, u64};
+use nom::error::ErrorKind;
+use nom::sequence::{pair, preceded, separated_pair, tuple};
+use nom::Parser;
 
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{anychar, char, space1};
-use nom::combinator::eof;
-use nom::multi::many1;
-use nom::multi::many_till;
-use nom::multi::separated_list0;
-use nom::sequence::preceded;
-use nom::{IResult, Parser};
+use crate::error::PuzzleError;
+use crate::log::{debug, trace};
+use crate::parsing::{labeled, lines_of, numbers, parse_all, sections};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
 #[derive(Debug)]
-struct Almanac {
-    seeds: Vec<u64>,
-    seed_to_soil: Vec<Range>,
-    soil_to_fertilizer: Vec<Range>,
-    fertilizer_to_water: Vec<Range>,
-    water_to_light: Vec<Range>,
-    light_to_temperature: Vec<Range>,
-    temperature_to_humidity: Vec<Range>,
-    humidity_to_location: Vec<Range>,
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub seed_to_soil: Vec<Range>,
+    pub soil_to_fertilizer: Vec<Range>,
+    pub fertilizer_to_water: Vec<Range>,
+    pub water_to_light: Vec<Range>,
+    pub light_to_temperature: Vec<Range>,
+    pub temperature_to_humidity: Vec<Range>,
+    pub humidity_to_location: Vec<Range>,
 }
 
-#[derive(Clone, Debug)]
-struct Range {
-    destination_start: u64,
-    source_start: u64,
-    width: u64,
+#[derive(Debug)]
+pub struct Range {
+    pub destination_start: u64,
+    pub source_start: u64,
+    pub width: u64,
 }
 
 impl Almanac {
@@ -164,7 +159,7 @@ impl Almanac {
         let temperature = find_ranges_in_ranges(&light, &self.light_to_temperature);
         let humidity = find_ranges_in_ranges(&temperature, &self.temperature_to_humidity);
         let location = find_ranges_in_ranges(&humidity, &self.humidity_to_location);
-        eprintln!(
+        debug!(
           "seed:{:?}, soil:{:?}, fertilizer:{:?}, water:{:?}, light:{:?}, temperature:{:?}, humidity:{:?}, location:{:?}",
           seed, soil, fertilizer, water, light, temperature, humidity, location
         );
@@ -179,7 +174,7 @@ impl Almanac {
         let temperature = find_in_ranges(light, &self.light_to_temperature);
         let humidity = find_in_ranges(temperature, &self.temperature_to_humidity);
         let location = find_in_ranges(humidity, &self.humidity_to_location);
-        eprintln!(
+        debug!(
           "seed:{}, soil:{}, fertilizer:{}, water:{}, light:{}, temperature:{}, humidity:{}, location:{}",
           seed, soil, fertilizer, water, light, temperature, humidity, location
         );
@@ -187,7 +182,8 @@ impl Almanac {
     }
 }
 
-fn find_in_ranges(value: u64, ranges: &[Range]) -> u64 {
+/// Maps `value` through the first range containing it, or to itself.
+pub fn find_in_ranges(value: u64, ranges: &[Range]) -> u64 {
     for range in ranges {
         if value >= range.source_start && value < range.source_start + range.width {
             return range.destination_start + (value - range.source_start);
@@ -196,18 +192,25 @@ fn find_in_ranges(value: u64, ranges: &[Range]) -> u64 {
     value
 }
 
-fn find_ranges_in_ranges(needles: &[(u64, u64)], ranges: &[Range]) -> Vec<(u64, u64)> {
-    eprintln!("needles:{:?}, ranges:{:?}", needles, ranges);
+/// Maps `(start, width)` spans through `ranges`, splitting them where they
+/// straddle a range boundary. Empty spans and ranges are left out: they
+/// cover no value, and `find_overlaps` would map an empty span as if it
+/// covered its start.
+pub fn find_ranges_in_ranges(needles: &[(u64, u64)], ranges: &[Range]) -> Vec<(u64, u64)> {
+    trace!("needles:{:?}, ranges:{:?}", needles, ranges);
     let mut out = vec![];
-    for &needle in needles {
+    for &needle in needles.iter().filter(|&&(_, width)| width > 0) {
         let mut curr_needles = vec![needle];
-        for range in ranges {
+        for range in ranges.iter().filter(|range| range.width > 0) {
             let mut next_needles = Vec::new();
             for curr_needle in curr_needles {
                 let (prec, overlap, succ) = find_overlaps(curr_needle, range);
-                eprintln!(
+                trace!(
                     "curr:{:?} prec:{:?} overlap:{:?} succ:{:?}",
-                    curr_needle, prec, overlap, succ
+                    curr_needle,
+                    prec,
+                    overlap,
+                    succ
                 );
                 if let Some(overlap) = overlap {
                     out.push(overlap);
@@ -226,14 +229,18 @@ fn find_ranges_in_ranges(needles: &[(u64, u64)], ranges: &[Range]) -> Vec<(u64,
     out
 }
 
-fn find_overlaps(
+/// The parts of a needle preceding, overlapping and succeeding a range.
+pub type Overlaps = (Option<(u64, u64)>, Option<(u64, u64)>, Option<(u64, u64)>);
+
+/// Splits a `(start, width)` needle around `range`, mapping the overlap.
+pub fn find_overlaps(
     (ns, nw): (u64, u64),
     &Range {
         destination_start,
         source_start,
         width,
     }: &Range,
-) -> (Option<(u64, u64)>, Option<(u64, u64)>, Option<(u64, u64)>) {
+) -> Overlaps {
     if ns + nw - 1 < source_start {
         // left
         (Some((ns, nw)), None, None)
@@ -267,73 +274,144 @@ fn find_overlaps(
     }
 }
 
-pub fn part1(file_name: &str) -> u64 {
-    *parse_almanac(read_to_string(file_name).unwrap().as_str())
-        .unwrap()
-        .1
+pub fn part1(almanac: &Almanac) -> Result<u64, PuzzleError> {
+    almanac
         .locations()
-        .iter()
+        .into_iter()
         .min()
-        .unwrap()
+        .ok_or_else(|| PuzzleError::invalid("no seeds"))
 }
 
-pub fn part2(file_name: &str) -> u64 {
-    parse_almanac(read_to_string(file_name).unwrap().as_str())
-        .unwrap()
-        .1
+pub fn part2(almanac: &Almanac) -> Result<u64, PuzzleError> {
+    almanac
         .location_ranges()
         .iter()
         .map(|t| t.0)
         .min()
-        .unwrap()
+        .ok_or_else(|| PuzzleError::invalid("no seed ranges"))
 }
 
-fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
-    let number = nom::character::complete::u64;
-    let mut parse_seeds = preceded(tag("seeds: "), separated_list0(space1, number));
-    let range = separated_list0(space1, number).map(|nums| Range {
-        destination_start: nums[0],
-        source_start: nums[1],
-        width: nums[2],
-    });
-    let parse_map = preceded(
-        many_till(anychar, tag(" map:")),
-        many_till(preceded(char('\n'), range), alt((tag("\n\n"), eof))),
-    )
-    .map(|(m, _)| m);
-    let (remaining, seeds) = parse_seeds(input)?;
-    let (remaining, maps) = preceded(tag("\n\n"), many1(parse_map))(remaining)?;
-    Ok((
-        remaining,
-        Almanac {
-            seeds,
-            seed_to_soil: maps[0].clone(),
-            soil_to_fertilizer: maps[1].clone(),
-            fertilizer_to_water: maps[2].clone(),
-            water_to_light: maps[3].clone(),
-            light_to_temperature: maps[4].clone(),
-            temperature_to_humidity: maps[5].clone(),
-            humidity_to_location: maps[6].clone(),
+pub fn parse(input: &str) -> Result<Almanac, PuzzleError> {
+    let range = tuple((u64, preceded(space1, u64), preceded(space1, u64))).map(
+        |(destination_start, source_start, width)| Range {
+            destination_start,
+            source_start,
+            width,
         },
-    ))
+    );
+    // "seed-to-soil map:" and its ranges.
+    let map = preceded(
+        pair(is_not(" \r\n"), pair(tag(" map:"), line_ending)),
+        lines_of(range),
+    );
+    let (seeds, maps) = parse_all(
+        input,
+        separated_pair(
+            labeled("seeds:", numbers),
+            pair(line_ending, line_ending),
+            sections(map),
+        ),
+    )?;
+    let end = &input[input.trim_end().len()..];
+    let mut maps = maps.into_iter();
+    let mut next_map = || {
+        maps.next()
+            .ok_or_else(|| PuzzleError::parse(input, end, ErrorKind::Count))
+    };
+    Ok(Almanac {
+        seeds,
+        seed_to_soil: next_map()?,
+        soil_to_fertilizer: next_map()?,
+        fertilizer_to_water: next_map()?,
+        water_to_light: next_map()?,
+        light_to_temperature: next_map()?,
+        temperature_to_humidity: next_map()?,
+        humidity_to_location: next_map()?,
+    })
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    part2(&parse(input)?)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Almanac {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2(self)?.into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day5_test_input.txt"), 35)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day5_input.txt"), 424490994)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 35)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day5_test_input.txt"), 46)
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 46)
     }
     #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day5_input.txt"), 15290096)
+    fn empty_seed_ranges() {
+        let almanac = super::parse(&EXAMPLE.replace("79 14 55 13", "79 0 55 13")).unwrap();
+        assert_eq!(super::part2(&almanac).unwrap(), 56);
+        let almanac = super::parse(&EXAMPLE.replace("79 14 55 13", "79 0")).unwrap();
+        assert_eq!(
+            super::part2(&almanac).unwrap_err().to_string(),
+            "<input>: no seed ranges"
+        );
     }
 }
@@ -41,8 +41,6 @@ To see how much margin of error you have, determine the number of ways you can b
 
 Determine the number of ways you could beat the record in each race. What do you get if you multiply these numbers together?
 
-Your puzzle answer was 633080.
-
 The first half of this puzzle is complete! It provides one gold star: *
 
 --- Part Two ---
@@ -59,86 +57,151 @@ Distance:  940200
 Now, you have to figure out how many ways there are to win this single race. In this example, the race lasts for 71530 milliseconds and the record distance you need to beat is 940200 millimeters. You could hold the button anywhere from 14 to 71516 milliseconds and beat the record, a total of 71503 ways!
 
 How many ways can you beat the record in this one much longer race?
-
-Your puzzle answer was 20048741.
 */
 
-use std::fs::read_to_string;
-
-pub fn part1(file_name: &str) -> u64 {
-    let input = read_to_string(file_name).unwrap();
-    let lines: Vec<_> = input.lines().collect();
-    let times: Vec<u64> = lines[0]
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| n.parse().unwrap())
-        .collect();
-    let distances: Vec<u64> = lines[1]
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| n.parse().unwrap())
-        .collect();
-    let mut product = 1;
-    for (&time, &distance) in times.iter().zip(distances.iter()) {
-        let mut count = 0;
-        for speed in 1..time {
-            if speed * (time - speed) > distance {
-                count += 1
-            }
-        }
-        product *= count;
+use nom::character::complete::{digit1, line_ending, space1};
+use nom::error::ErrorKind;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use crate::error::PuzzleError;
+use crate::math::isqrt;
+use crate::parsing::{labeled, parse_all};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
+/// The races on the sheet, along with the single race it describes when
+/// read without the bad kerning.
+pub struct Races {
+    pub races: Vec<(u64, u64)>,
+    pub kerned: (u64, u64),
+}
+
+pub fn parse(input: &str) -> Result<Races, PuzzleError> {
+    let (times, distances) = parse_races(input)?;
+    let races = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(&time, &distance)| Ok((to_number(input, time)?, to_number(input, distance)?)))
+        .collect::<Result<_, PuzzleError>>()?;
+    let kerned = (
+        join_numbers(input, &times)?,
+        join_numbers(input, &distances)?,
+    );
+    Ok(Races { races, kerned })
+}
+
+pub fn part1(races: &Races) -> u64 {
+    races
+        .races
+        .iter()
+        .map(|&(time, distance)| ways_to_win(time, distance))
+        .product()
+}
+
+pub fn part2(races: &Races) -> u64 {
+    let (time, distance) = races.kerned;
+    ways_to_win(time, distance)
+}
+
+/// How many whole milliseconds of holding the button beat `distance`. Holding
+/// for `h` travels `h * (time - h)`, so the winning holds lie strictly between
+/// the roots of `h² - time·h + distance`.
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    let (time, distance) = (u128::from(time), u128::from(distance));
+    let travels = |hold: u128| hold * (time - hold);
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+    // The integer root can land a step off the real root either way.
+    let mut shortest = (time - isqrt(discriminant)) / 2;
+    while shortest <= time / 2 && travels(shortest) <= distance {
+        shortest += 1;
+    }
+    while shortest > 0 && travels(shortest - 1) > distance {
+        shortest -= 1;
     }
-    product
+    if shortest > time / 2 {
+        return 0;
+    }
+    (time - 2 * shortest + 1) as u64
 }
 
-pub fn part2(file_name: &str) -> u64 {
-    let input = read_to_string(file_name).unwrap();
-    let lines: Vec<_> = input.lines().collect();
-    let time: u64 = lines[0]
-        .split(':')
-        .skip(1)
-        .next()
-        .unwrap()
-        .chars()
-        .filter(|c| c.is_numeric())
-        .collect::<String>()
+/// The digit runs of the time and distance lines, kept as text since part 2
+/// reads each line as a single number.
+fn parse_races(input: &str) -> Result<(Vec<&str>, Vec<&str>), PuzzleError> {
+    let digits = || separated_list1(space1, digit1);
+    parse_all(
+        input,
+        separated_pair(
+            labeled("Time:", digits()),
+            line_ending,
+            labeled("Distance:", digits()),
+        ),
+    )
+}
+
+fn to_number(input: &str, digits: &str) -> Result<u64, PuzzleError> {
+    digits
         .parse()
-        .unwrap();
-    let distance: u64 = lines[1]
-        .split(':')
-        .skip(1)
-        .next()
-        .unwrap()
-        .chars()
-        .filter(|c| c.is_numeric())
-        .collect::<String>()
+        .map_err(|_| PuzzleError::parse(input, digits, ErrorKind::TooLarge))
+}
+
+fn join_numbers(input: &str, runs: &[&str]) -> Result<u64, PuzzleError> {
+    runs.concat()
         .parse()
-        .unwrap();
-    let mut count = 0;
-    for speed in 1..time {
-        if speed * (time - speed) > distance {
-            count += 1
-        }
+        .map_err(|_| PuzzleError::parse(input, runs[0], ErrorKind::TooLarge))
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part1(&parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    Ok(part2(&parse(input)?))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Races {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self).into(),
+            Part::Two => part2(self).into(),
+        })
     }
-    count
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day6_test_input.txt"), 288)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day6_input.txt"), 633080)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 288)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day6_test_input.txt"), 71503)
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 71503)
     }
     #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day6_input.txt"), 20048741)
+    fn ways_to_win() {
+        for time in 0..40 {
+            for distance in 0..400 {
+                let brute = (1..time).filter(|h| h * (time - h) > distance).count();
+                assert_eq!(super::ways_to_win(time, distance), brute as u64);
+            }
+        }
     }
 }
@@ -51,8 +51,6 @@ Now, you can determine the total winnings of this set of hands by adding up the
 
 Find the rank of every hand in your set. What are the total winnings?
 
-Your puzzle answer was 250602641.
-
 --- Part Two ---
 To make things a little more interesting, the Elf introduces one additional rule. Now, J cards are jokers - wildcards that can act like whatever card would make the hand the strongest type possible.
 
@@ -73,61 +71,61 @@ T55J5, KTJJT, and QQQJA are now all four of a kind! T55J5 gets rank 3, QQQJA get
 With the new joker rule, the total winnings in this example are 5905.
 
 Using the new joker rule, find the rank of every hand in your set. What are the new total winnings?
-
-Your puzzle answer was 251037509.
 */
 
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fs::read_to_string;
-
-pub fn part1(file_name: &str) -> usize {
-    let mut hands_and_bids: Vec<(String, usize)> = read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_ascii_whitespace();
-            (
-                parts.next().unwrap().to_string(),
-                parts.next().unwrap().parse().unwrap(),
-            )
-        })
-        .collect();
-    hands_and_bids.sort_by(|(a, _), (b, _)| cmp_hands(a, b));
-    hands_and_bids
+
+use nom::bytes::complete::take_while_m_n;
+use nom::character::complete::{space1, u64};
+use nom::combinator::map;
+use nom::sequence::separated_pair;
+
+use crate::error::PuzzleError;
+use crate::log::trace;
+use crate::parsing::parse_lines;
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
+#[derive(Debug)]
+pub struct Hand {
+    pub cards: String,
+    pub bid: usize,
+}
+
+pub fn part1(hands: &[Hand]) -> usize {
+    let mut hands: Vec<_> = hands.iter().collect();
+    hands.sort_by(|a, b| cmp_hands(&a.cards, &b.cards));
+    hands
         .iter()
         .enumerate()
-        .map(|(i, (_, bid))| (i + 1) * bid)
-        .map(|w| {
-            eprintln!("{:?}", w);
-            w
-        })
+        .map(|(i, hand)| (i + 1) * hand.bid)
+        .inspect(|w| trace!("{:?}", w))
         .sum()
 }
 
-pub fn part2(file_name: &str) -> usize {
-    let mut hands_and_bids: Vec<(String, usize)> = read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_ascii_whitespace();
-            (
-                parts.next().unwrap().to_string(),
-                parts.next().unwrap().parse().unwrap(),
-            )
-        })
-        .collect();
-    hands_and_bids.sort_by(|(a, _), (b, _)| cmp_hands_joker(a, b));
-    hands_and_bids
+pub fn part2(hands: &[Hand]) -> usize {
+    let mut hands: Vec<_> = hands.iter().collect();
+    hands.sort_by(|a, b| cmp_hands_joker(&a.cards, &b.cards));
+    hands
         .iter()
         .enumerate()
-        .map(|(i, (_, bid))| (i + 1) * bid)
-        .map(|w| {
-            eprintln!("{:?}", w);
-            w
-        })
+        .map(|(i, hand)| (i + 1) * hand.bid)
+        .inspect(|w| trace!("{:?}", w))
         .sum()
 }
+
+pub fn parse(input: &str) -> Result<Vec<Hand>, PuzzleError> {
+    let cards = take_while_m_n(5, 5, |c| "AKQJT98765432".contains(c));
+    let hand = map(
+        separated_pair(cards, space1, u64),
+        |(cards, bid): (&str, u64)| Hand {
+            cards: cards.to_string(),
+            bid: bid as usize,
+        },
+    );
+    parse_lines(input, hand)
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 enum Type {
     High,
@@ -172,7 +170,7 @@ fn hand_type(hand: &str) -> Type {
 fn cmp_hands(a: &str, b: &str) -> Ordering {
     let a_type = hand_type(a);
     let b_type = hand_type(b);
-    eprintln!("{} {:?} {} {:?}", a, a_type, b, b_type);
+    trace!("{} {:?} {} {:?}", a, a_type, b, b_type);
     if a_type == b_type {
         map_face(b).as_str().cmp(&map_face(a))
     } else {
@@ -183,7 +181,7 @@ fn cmp_hands(a: &str, b: &str) -> Ordering {
 fn cmp_hands_joker(a: &str, b: &str) -> Ordering {
     let a_type = hand_type_joker(a);
     let b_type = hand_type_joker(b);
-    eprintln!("{} {:?} {} {:?}", a, a_type, b, b_type);
+    trace!("{} {:?} {} {:?}", a, a_type, b, b_type);
     if a_type == b_type {
         map_face_joker(b).as_str().cmp(&map_face_joker(a))
     } else {
@@ -257,22 +255,50 @@ fn map_face_joker(hand: &str) -> String {
         .collect()
 }
 
+pub fn solve_part1(input: &str) -> Result<usize, PuzzleError> {
+    Ok(part1(&parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, PuzzleError> {
+    Ok(part2(&parse(input)?))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Vec<Hand> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self).into(),
+            Part::Two => part2(self).into(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day7_test_input.txt"), 6440)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day7_input.txt"), 250602641)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 6440)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day7_test_input.txt"), 5905)
-    }
-    #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day7_input.txt"), 251037509)
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 5905)
     }
 }
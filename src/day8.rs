@@ -30,8 +30,6 @@ BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 Starting at AAA, follow the left/right instructions. How many steps are required to reach ZZZ?
 
-Your puzzle answer was 13301.
-
 --- Part Two ---
 The sandstorm is upon you and you aren't any closer to escaping the wasteland. You had the camel follow the instructions, but you've barely left your starting position. It's going to take significantly more steps to escape!
 
@@ -63,114 +61,178 @@ Step 6: You choose all of the right paths, leading you to 11Z and 22Z.
 So, in this example, you end up entirely on nodes that end in Z after 6 steps.
 
 Simultaneously start on every node that ends with A. How many steps does it take before you're only on nodes that end with Z?
-
-Your puzzle answer was 7309459565207.
 */
 
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use std::collections::{HashMap, HashSet};
+
+use nom::bytes::complete::take_while1;
+use nom::character::complete::line_ending;
+use nom::error::ErrorKind;
+use nom::sequence::{pair, separated_pair};
+
+use crate::error::PuzzleError;
+use crate::log::debug;
+use crate::math::lcm_all;
+use crate::parsing::{key_pair, lines_of, parse_all};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
+/// The left/right instructions and the network of nodes they navigate.
+#[derive(Debug)]
+pub struct Network {
+    pub instructions: String,
+    pub graph: HashMap<String, (String, String)>,
+}
+
+pub fn part1(network: &Network) -> Result<u64, PuzzleError> {
+    for node in ["AAA", "ZZZ"] {
+        if !network.graph.contains_key(node) {
+            return Err(PuzzleError::invalid(format!("no node {node}")));
+        }
+    }
+    steps(network, "AAA", |node| node == "ZZZ")
+        .ok_or_else(|| PuzzleError::invalid("ZZZ cannot be reached from AAA"))
+}
+
+pub fn part2(network: &Network) -> Result<u64, PuzzleError> {
+    let mut counts = Vec::new();
+    for start in network.graph.keys().filter(|s| s.ends_with('A')) {
+        let count = steps(network, start, |node| node.ends_with('Z')).ok_or_else(|| {
+            PuzzleError::invalid(format!("no node ending in Z can be reached from {start}"))
+        })?;
+        counts.push(count);
+    }
+    debug!("{counts:?}");
+    lcm_all(&counts).ok_or_else(|| PuzzleError::invalid("the ghosts take more than 2^64 steps"))
+}
 
-pub fn part1(file_name: &str) -> usize {
-    let (instructions, graph) = parse_graph(read_to_string(file_name).unwrap().as_str());
-    let mut count = 0;
-    let mut current = "AAA".to_string();
-    for i in instructions.chars().cycle() {
-        if current == "ZZZ" {
-            break;
+/// How many steps the instructions take from `start` to a node `end`
+/// accepts, or `None` if they never get there: once a node comes round
+/// again at the same instruction, the walk only repeats itself.
+fn steps(network: &Network, start: &str, end: impl Fn(&str) -> bool) -> Option<u64> {
+    let mut seen = HashSet::new();
+    let mut current = start;
+    let instructions = network.instructions.chars().enumerate().cycle();
+    for (count, (index, i)) in (0..).zip(instructions) {
+        if end(current) {
+            return Some(count);
         }
-        let (left, right) = &graph[&current];
-        if i == 'L' {
-            current = left.clone();
-        } else {
-            current = right.clone();
+        if !seen.insert((current, index)) {
+            return None;
         }
-        count += 1;
+        let (left, right) = &network.graph[current];
+        current = if i == 'L' { left } else { right };
     }
-    count
+    None
 }
 
-pub fn part2(file_name: &str) -> usize {
-    let (instructions, graph) = parse_graph(read_to_string(file_name).unwrap().as_str());
-    let starts: Vec<_> = graph
-        .keys()
-        .filter(|s| s.chars().last().unwrap() == 'A')
-        .cloned()
+pub fn parse(input: &str) -> Result<Network, PuzzleError> {
+    let instructions = take_while1(|c| c == 'L' || c == 'R');
+    let (instructions, vertices) = parse_all(
+        input,
+        separated_pair(
+            instructions,
+            pair(line_ending, line_ending),
+            lines_of(key_pair),
+        ),
+    )?;
+    let graph: HashMap<_, _> = vertices
+        .iter()
+        .map(|&(node, (left, right))| (node.into(), (left.into(), right.into())))
         .collect();
-    let mut counts = vec![0; starts.len()];
-    let mut current;
-    for (index, start) in starts.iter().enumerate() {
-        current = start;
-        for i in instructions.chars().cycle() {
-            if current.chars().last().unwrap() == 'Z' {
-                break;
-            }
-            let (left, right) = &graph[current];
-            if i == 'L' {
-                current = left;
-            } else {
-                current = right;
-            }
-            counts[index] += 1;
+    for (_, (left, right)) in &vertices {
+        if let Some(missing) = [left, right].into_iter().find(|n| !graph.contains_key(**n)) {
+            return Err(PuzzleError::parse(input, missing, ErrorKind::Verify));
         }
     }
-    eprintln!("{counts:?}");
-    lcm(&counts)
+    Ok(Network {
+        instructions: instructions.into(),
+        graph,
+    })
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
 }
 
-fn lcm(nums: &[usize]) -> usize {
-  nums.iter().fold(1, |acc, &a| acc * a / gcd(a, acc))
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    part2(&parse(input)?)
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-  use std::ops::Rem;
-  let  (mut g, mut l) = if a > b { (b, a) } else { (a, b) };
-  let mut prev = l;
-  let mut curr = g;
-  while curr != 0 {
-    prev = curr;
-    curr = g.rem(l);
-    g = l;
-    l = curr;
-  }
-  return prev
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
 }
 
-fn parse_graph(input: &str) -> (String, HashMap<String, (String, String)>) {
-    let mut lines = input.lines();
-    let instructions = lines.next().unwrap();
-    let vertices: Vec<_> = lines
-        .skip(1)
-        .map(|line| {
-            (
-                line[0..3].to_owned(),
-                (line[7..10].into(), line[12..15].into()),
-            )
+impl Parsed for Network {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2(self)?.into(),
         })
-        .collect();
-    let graph = HashMap::from_iter(vertices.into_iter());
-    (instructions.into(), graph)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE3: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day8_test_input.txt"), 2)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 2)
     }
     #[test]
     fn part1_example2() {
-        assert_eq!(super::part1("src/day8_test_input2.txt"), 6)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day8_input.txt"), 13301)
+        assert_eq!(super::solve_part1(EXAMPLE2).unwrap(), 6)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day8_test_input3.txt"), 6)
+        assert_eq!(super::solve_part2(EXAMPLE3).unwrap(), 6)
     }
     #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day8_input.txt"), 7309459565207)
+    fn unreachable() {
+        let network = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            super::solve_part1(network).unwrap_err().to_string(),
+            "<input>: ZZZ cannot be reached from AAA"
+        );
+        assert_eq!(
+            super::solve_part2(network).unwrap_err().to_string(),
+            "<input>: no node ending in Z can be reached from AAA"
+        );
     }
 }
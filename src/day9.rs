@@ -64,8 +64,6 @@ If you find the next value for each history in this example and add them togethe
 
 Analyze your OASIS report and extrapolate the next value for each history. What is the sum of these extrapolated values?
 
-Your puzzle answer was 1861775706.
-
 --- Part Two ---
 Of course, it would be nice to have even more history included in your report. Surely it's safe to just extrapolate backwards as well, right?
 
@@ -84,36 +82,29 @@ Doing this for the remaining example data above results in previous values of -3
 
 Analyze your OASIS report again, this time extrapolating the previous value for each history. What is the sum of these extrapolated values?
 
-Your puzzle answer was 1082.
-
 */
 
-use std::fs::read_to_string;
+use crate::error::PuzzleError;
+use crate::parsing::{parse_lines, signed_numbers};
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
 
-pub fn part1(file_name: &str) -> i64 {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| extrapolate(&parse_line(line)))
-        .sum()
+pub fn part1(histories: &[Vec<i64>]) -> i64 {
+    histories.iter().map(|history| extrapolate(history)).sum()
 }
 
-pub fn part2(file_name: &str) -> i64 {
-    read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut parsed = parse_line(line);
-            parsed.reverse();
-            extrapolate(&parsed)
+pub fn part2(histories: &[Vec<i64>]) -> i64 {
+    histories
+        .iter()
+        .map(|history| {
+            let mut history = history.clone();
+            history.reverse();
+            extrapolate(&history)
         })
         .sum()
 }
 
-fn parse_line(line: &str) -> Vec<i64> {
-    line.split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, PuzzleError> {
+    parse_lines(input, signed_numbers)
 }
 
 fn extrapolate(history: &[i64]) -> i64 {
@@ -126,25 +117,51 @@ fn extrapolate(history: &[i64]) -> i64 {
         .zip(history.iter().skip(1))
         .map(|(a, b)| b - a)
         .collect();
-    return last + extrapolate(&diffs);
+    last + extrapolate(&diffs)
+}
+
+pub fn solve_part1(input: &str) -> Result<i64, PuzzleError> {
+    Ok(part1(&parse(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<i64, PuzzleError> {
+    Ok(part2(&parse(input)?))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Vec<Vec<i64>> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self).into(),
+            Part::Two => part2(self).into(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
     #[test]
     fn part1_example() {
-        assert_eq!(super::part1("src/day9_test_input.txt"), 114)
-    }
-    #[test]
-    fn part1_actual() {
-        assert_eq!(super::part1("src/day9_input.txt"), 1861775706)
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 114)
     }
     #[test]
     fn part2_example() {
-        assert_eq!(super::part2("src/day9_test_input.txt"), 2)
-    }
-    #[test]
-    fn part2_actual() {
-        assert_eq!(super::part2("src/day9_input.txt"), 1082)
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 2)
     }
 }
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+
+use nom::error::ErrorKind;
+
+/// Why a puzzle could not be solved, with enough context to find the culprit
+/// in the input.
+#[derive(Debug)]
+pub enum PuzzleError {
+    /// The input file could not be read.
+    Io { file: String, error: io::Error },
+    /// The input does not have the shape the puzzle describes.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        kind: ErrorKind,
+    },
+    /// The input parses, but describes something without a solution.
+    Invalid {
+        file: Option<String>,
+        reason: String,
+    },
+    /// A day-specific parameter has a value the day cannot use.
+    Param {
+        name: String,
+        value: String,
+        reason: String,
+    },
+    /// The solver panicked or ran out of time.
+    Aborted { reason: String },
+}
+
+impl PuzzleError {
+    /// A parse error at `rest`, which must be a subslice of `input`.
+    pub fn parse(input: &str, rest: &str, kind: ErrorKind) -> Self {
+        let offset = (rest.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        PuzzleError::Parse {
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    /// Converts a nom error raised while parsing a subslice of `input`.
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => PuzzleError::parse(input, e.input, e.code),
+            nom::Err::Incomplete(_) => {
+                PuzzleError::parse(input, &input[input.len()..], ErrorKind::Complete)
+            }
+        }
+    }
+
+    pub fn invalid(reason: impl Into<String>) -> Self {
+        PuzzleError::Invalid {
+            file: None,
+            reason: reason.into(),
+        }
+    }
+
+    /// Records the file the offending input was read from.
+    pub fn in_file(mut self, file_name: &str) -> Self {
+        if let PuzzleError::Parse { file, .. } | PuzzleError::Invalid { file, .. } = &mut self {
+            *file = Some(file_name.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::Io { file, error } => write!(f, "{file}: {error}"),
+            PuzzleError::Parse {
+                file,
+                line,
+                column,
+                kind,
+            } => write!(
+                f,
+                "{}:{line}:{column}: unexpected input ({})",
+                file.as_deref().unwrap_or("<input>"),
+                kind.description()
+            ),
+            PuzzleError::Invalid { file, reason } => {
+                write!(f, "{}: {reason}", file.as_deref().unwrap_or("<input>"))
+            }
+            PuzzleError::Param {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value `{value}` for `{name}`: {reason}"),
+            PuzzleError::Aborted { reason } => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+/// Reads `file_name` and runs `solve` on its contents, attributing any error
+/// to the file.
+pub fn with_input<T>(
+    file_name: &str,
+    solve: impl FnOnce(&str) -> Result<T, PuzzleError>,
+) -> Result<T, PuzzleError> {
+    let input = read_to_string(file_name).map_err(|error| PuzzleError::Io {
+        file: file_name.to_string(),
+        error,
+    })?;
+    solve(&input).map_err(|e| e.in_file(file_name))
+}
+
+#[cfg(test)]
+mod test {
+    use nom::bytes::complete::tag;
+    use nom::error::ErrorKind;
+
+    use super::PuzzleError;
+
+    #[test]
+    fn position() {
+        let input = "Game 1: 3 blue\nGame 2: 4 purple";
+        let line = input.lines().nth(1).unwrap();
+        let error = tag::<_, _, nom::error::Error<_>>("Game 2: 4 blue")(line).unwrap_err();
+        match PuzzleError::from_nom(input, error).in_file("day2.txt") {
+            PuzzleError::Parse {
+                file,
+                line,
+                column,
+                kind,
+            } => {
+                assert_eq!(file.as_deref(), Some("day2.txt"));
+                assert_eq!((line, column), (2, 1));
+                assert_eq!(kind, ErrorKind::Tag);
+            }
+            e => panic!("unexpected {e:?}"),
+        }
+        let rest = &line[10..];
+        assert_eq!(
+            PuzzleError::parse(input, rest, ErrorKind::Alpha).to_string(),
+            "<input>:2:11: unexpected input (Alphabetic)"
+        );
+    }
+}
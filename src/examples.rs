@@ -0,0 +1,242 @@
+//! Worked examples pulled out of the puzzle text heading each `dayN.rs`.
+//!
+//! For each part, the expected answer is the last number in the paragraph
+//! before the part's question, and the example is the nearest block of input
+//! above it that the day's own parser accepts. Part two falls back to part
+//! one's example when it reuses it without repeating it.
+//!
+//! `my-project examples --write` renders what it finds into
+//! `src/examples/generated.rs`, which the tests below run.
+
+use std::fmt::Write;
+
+use crate::answers::source_file;
+use crate::error::{with_input, PuzzleError};
+use crate::solution::{Answer, Part, Solution, SOLUTIONS};
+
+#[cfg(test)]
+mod generated;
+
+/// Where the generated table of examples is written, relative to the crate
+/// root.
+pub const GENERATED_FILE: &str = "src/examples/generated.rs";
+
+/// Parts whose examples the rules above get wrong.
+const SKIPPED: &[(u8, Part)] = &[
+    // The nearest grid above the answer is the universe already expanded.
+    (11, Part::One),
+    // The example only gives answers for expanding by 10 and 100.
+    (11, Part::Two),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Answer,
+}
+
+/// A line of the story rather than of an example: a sentence of a few words,
+/// or a shorter one introducing what follows, such as "For example:". Input
+/// lines such as day 5's "seed-to-soil map:" are short and lowercase.
+fn is_prose(line: &str) -> bool {
+    let line = line.trim();
+    let words = line.split_whitespace().count();
+    let introduces =
+        line.ends_with(':') && (words >= 3 || words == 2 && line.starts_with(char::is_uppercase));
+    introduces || words >= 5 && line.ends_with(['.', '?', '!'])
+}
+
+/// Runs of input-like lines. A blank line only ends a block when prose
+/// follows it, as inputs such as day 5's have blank lines of their own.
+fn blocks(lines: &[&str]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut block = Vec::new();
+    for (i, &line) in lines.iter().enumerate() {
+        let next_is_input = lines
+            .get(i + 1)
+            .is_some_and(|next| !next.trim().is_empty() && !is_prose(next));
+        let continues = if line.trim().is_empty() {
+            !block.is_empty() && next_is_input
+        } else {
+            !is_prose(line)
+        };
+        if continues {
+            block.push(line);
+        } else if !block.is_empty() {
+            blocks.push(block.join("\n"));
+            block.clear();
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block.join("\n"));
+    }
+    blocks
+}
+
+/// The last number in `line`, ignoring digit groups within words and a
+/// closing remark such as "you get 288 (4 * 8 * 9).".
+fn last_number(line: &str) -> Option<i128> {
+    let line = line.trim_end().trim_end_matches('.');
+    let line = match line.strip_suffix(')').and_then(|l| l.rsplit_once('(')) {
+        Some((before, _)) if !before.trim().is_empty() => before,
+        _ => line,
+    };
+    line.rsplit(|c: char| !c.is_ascii_alphanumeric())
+        .find_map(|word| word.parse().ok())
+}
+
+/// The example and answer in one part's share of the puzzle text, as far as
+/// they can be found.
+fn extract_part(solution: &dyn Solution, lines: &[&str]) -> (Option<String>, Option<i128>) {
+    let end = lines
+        .iter()
+        .position(|line| line.starts_with("Your puzzle answer was"))
+        .unwrap_or(lines.len());
+    let Some(question) = lines[..end]
+        .iter()
+        .rposition(|line| line.trim_end().ends_with('?'))
+    else {
+        return (None, None);
+    };
+    let result = lines[..question]
+        .iter()
+        .rposition(|line| is_prose(line) && last_number(line).is_some());
+    let parses = |block: &String| solution.parse(block).is_ok();
+    let input = match result {
+        // "here is a situation that takes 6 steps to reach ZZZ:"
+        Some(i) if lines[i].trim_end().ends_with(':') => {
+            blocks(&lines[i + 1..question]).into_iter().find(parses)
+        }
+        _ => blocks(&lines[..result.unwrap_or(question)])
+            .into_iter()
+            .rev()
+            .find(parses),
+    };
+    (input, result.and_then(|i| last_number(lines[i])))
+}
+
+/// The examples in a day's `source`, at most one per part.
+pub fn extract(solution: &dyn Solution, source: &str) -> Vec<Example> {
+    let Some(header) = source.strip_prefix("/*") else {
+        return Vec::new();
+    };
+    let header = header.split("*/").next().unwrap_or_default();
+    let lines: Vec<&str> = header.lines().collect();
+    let two = lines
+        .iter()
+        .position(|line| line.trim() == "--- Part Two ---")
+        .unwrap_or(lines.len());
+    let (input_one, answer_one) = extract_part(solution, &lines[..two]);
+    let (input_two, answer_two) = extract_part(solution, &lines[two..]);
+    let input_two = input_two.or_else(|| input_one.clone());
+    [
+        (Part::One, input_one, answer_one),
+        (Part::Two, input_two, answer_two),
+    ]
+    .into_iter()
+    .filter(|(part, _, _)| solution.parts().contains(part))
+    .filter(|&(part, _, _)| !SKIPPED.contains(&(solution.day(), part)))
+    .filter_map(|(part, input, answer)| {
+        Some(Example {
+            day: solution.day(),
+            part,
+            input: input?,
+            answer: Answer(answer?),
+        })
+    })
+    .collect()
+}
+
+/// Extracts the examples of every registered day from its source file.
+pub fn extract_all() -> Result<Vec<Example>, PuzzleError> {
+    let mut examples = Vec::new();
+    for &solution in SOLUTIONS {
+        examples.extend(with_input(&source_file(solution.day()), |source| {
+            Ok(extract(solution, source))
+        })?);
+    }
+    Ok(examples)
+}
+
+/// Renders `input` as a string literal, one line of input per line of
+/// source where the first line allows it.
+fn literal(input: &str) -> String {
+    if input.starts_with(char::is_whitespace) {
+        return format!("{input:?}");
+    }
+    let escaped = input.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"\\\n{escaped}\"")
+}
+
+/// Renders `examples` as the source of `GENERATED_FILE`.
+pub fn generate(examples: &[Example]) -> String {
+    let mut out = String::from(
+        "// Generated by `my-project examples --write` from the puzzle headers.\n\
+         // Do not edit by hand.\n\n\
+         use crate::solution::Part;\n\n\
+         pub static EXAMPLES: &[(u8, Part, &str, i128)] = &[\n",
+    );
+    for Example {
+        day,
+        part,
+        input,
+        answer,
+    } in examples
+    {
+        let _ = writeln!(
+            out,
+            "    (\n        {day},\n        Part::{part:?},\n        {},\n        {},\n    ),",
+            literal(input),
+            answer.0
+        );
+    }
+    out.push_str("];\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generated, Example};
+    use crate::solution::{self, Answer, Params};
+
+    #[test]
+    fn generated_examples() {
+        let failures: Vec<String> = generated::EXAMPLES
+            .iter()
+            .filter_map(|&(day, part, input, answer)| {
+                let solution = solution::find(day).unwrap();
+                let result = solution
+                    .parse(input)
+                    .and_then(|parsed| parsed.solve(part, &Params::default()));
+                match result {
+                    Ok(got) if got == Answer(answer) => None,
+                    Ok(got) => Some(format!(
+                        "day {day} part {part}: expected {answer}, got {got}"
+                    )),
+                    Err(e) => Some(format!("day {day} part {part}: {e}")),
+                }
+            })
+            .collect();
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn generated_is_current() {
+        let generated: Vec<Example> = generated::EXAMPLES
+            .iter()
+            .map(|&(day, part, input, answer)| Example {
+                day,
+                part,
+                input: input.to_string(),
+                answer: Answer(answer),
+            })
+            .collect();
+        assert_eq!(
+            generated,
+            super::extract_all().unwrap(),
+            "the puzzle headers changed; run `my-project examples --write`"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+// Generated by `my-project examples --write` from the puzzle headers.
+// Do not edit by hand.
+
+use crate::solution::Part;
+
+pub static EXAMPLES: &[(u8, Part, &str, i128)] = &[
+    (
+        1,
+        Part::One,
+        "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+        142,
+    ),
+    (
+        1,
+        Part::Two,
+        "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+        281,
+    ),
+    (
+        2,
+        Part::One,
+        "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        8,
+    ),
+    (
+        2,
+        Part::Two,
+        "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        2286,
+    ),
+    (
+        3,
+        Part::One,
+        "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+        4361,
+    ),
+    (
+        3,
+        Part::Two,
+        "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+        467835,
+    ),
+    (
+        4,
+        Part::One,
+        "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        13,
+    ),
+    (
+        4,
+        Part::Two,
+        "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        30,
+    ),
+    (
+        5,
+        Part::One,
+        "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4",
+        35,
+    ),
+    (
+        5,
+        Part::Two,
+        "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4",
+        46,
+    ),
+    (
+        6,
+        Part::One,
+        "\
+Time:      7  15   30
+Distance:  9  40  200",
+        288,
+    ),
+    (
+        6,
+        Part::Two,
+        "\
+Time:      71530
+Distance:  940200",
+        71503,
+    ),
+    (
+        7,
+        Part::One,
+        "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483",
+        6440,
+    ),
+    (
+        7,
+        Part::Two,
+        "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483",
+        5905,
+    ),
+    (
+        8,
+        Part::One,
+        "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+        6,
+    ),
+    (
+        8,
+        Part::Two,
+        "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        6,
+    ),
+    (
+        9,
+        Part::One,
+        "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45",
+        114,
+    ),
+    (
+        9,
+        Part::Two,
+        "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45",
+        2,
+    ),
+    (
+        10,
+        Part::One,
+        "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....",
+        4,
+    ),
+    (
+        10,
+        Part::Two,
+        "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJIF7FJ-
+L---JF-JLJIIIIFJLJJ7
+|F|F-JF---7IIIL7L|7|
+|FFJF7L7F-JF7IIL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        10,
+    ),
+    (
+        12,
+        Part::One,
+        "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1",
+        21,
+    ),
+];
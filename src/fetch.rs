@@ -0,0 +1,176 @@
+//! Downloading puzzle inputs into a local cache.
+//!
+//! An input is fetched at most once: when `dayN_input.txt` already exists in
+//! the inputs directory, in the clear or sealed, it is used as is, unless it
+//! is empty. Downloads authenticate with the
+//! session cookie of a logged-in browser, read from `AOC_SESSION`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::PuzzleError;
+use crate::http;
+use crate::vault;
+
+/// The puzzle site, without a trailing slash.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2023";
+
+/// The environment variables holding the session token and, optionally, a
+/// base URL to use instead of [`DEFAULT_BASE_URL`].
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Where the inputs are cached by default, matching
+/// [`crate::solution::default_input`].
+pub const DEFAULT_DIR: &str = "src";
+
+/// Where the input for `day` is cached under `dir`.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}_input.txt"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already in the cache, in the clear or sealed at this
+    /// path.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    pub base_url: String,
+    /// Only needed for inputs that are not cached yet.
+    pub session: Option<String>,
+    pub dir: PathBuf,
+}
+
+impl Fetcher {
+    /// The input for `day`, downloaded into the cache unless it is there
+    /// already.
+    pub fn fetch(&self, day: u8) -> Result<Fetched, PuzzleError> {
+        let path = input_path(&self.dir, day);
+        // `my-project new` leaves an empty input behind as a placeholder.
+        let placeholder = fs::metadata(&path).is_ok_and(|m| m.len() == 0);
+        if !placeholder {
+            let sealed = PathBuf::from(vault::sealed_path(&path.display().to_string()));
+            if path.exists() {
+                return Ok(Fetched::Cached(path));
+            }
+            if sealed.exists() {
+                return Ok(Fetched::Cached(sealed));
+            }
+        }
+        let url = format!("{}/day/{day}/input", self.base_url.trim_end_matches('/'));
+        let failed = |error| PuzzleError::Io {
+            file: url.clone(),
+            error,
+        };
+        let session = self.session.as_deref().ok_or_else(|| {
+            failed(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("no session token; set {SESSION_VAR}"),
+            ))
+        })?;
+        let cookie = http::session_cookie(session).map_err(failed)?;
+        let response = http::request("GET", &url, &[("Cookie", &cookie)], None)?;
+        if !response.is_success() {
+            return Err(failed(io::Error::other(format!(
+                "the server answered {} {}",
+                response.status, response.reason
+            ))));
+        }
+
+        // Write beside the cache entry and rename, so an interrupted
+        // download never leaves a partial input behind to be trusted later.
+        let saved = |error| PuzzleError::Io {
+            file: path.display().to_string(),
+            error,
+        };
+        fs::create_dir_all(&self.dir).map_err(saved)?;
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, &response.body).map_err(saved)?;
+        fs::rename(&partial, &path).map_err(saved)?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{Fetched, Fetcher};
+    use crate::http::stand_in;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("my-project-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (base_url, server) = stand_in(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n0 3 6 9\n".into(),
+        ]);
+        let dir = scratch("downloads-once");
+        let fetcher = Fetcher {
+            base_url,
+            session: Some("cafe".into()),
+            dir: dir.clone(),
+        };
+        let path = dir.join("day9_input.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "").unwrap();
+        assert_eq!(fetcher.fetch(9).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(fetcher.fetch(9).unwrap(), Fetched::Cached(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "0 3 6 9\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=cafe\r\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sealed_inputs_are_cached() {
+        let dir = scratch("sealed");
+        let fetcher = Fetcher {
+            base_url: "http://127.0.0.1:9".into(),
+            session: None,
+            dir: dir.clone(),
+        };
+        let sealed = dir.join("day3_input.txt.vault");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&sealed, "my-project vault 1\n").unwrap();
+        assert_eq!(fetcher.fetch(3).unwrap(), Fetched::Cached(sealed));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failures_are_not_cached() {
+        let (base_url, server) = stand_in(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n".into(),
+        ]);
+        let dir = scratch("failures");
+        let mut fetcher = Fetcher {
+            base_url,
+            session: Some("stale".into()),
+            dir: dir.clone(),
+        };
+        let error = fetcher.fetch(1).unwrap_err().to_string();
+        assert!(error.ends_with("/2023/day/1/input: the server answered 400 Bad Request"));
+        assert!(!dir.join("day1_input.txt").exists());
+        server.join().unwrap();
+
+        fetcher.session = None;
+        let error = fetcher.fetch(1).unwrap_err().to_string();
+        assert!(
+            error.ends_with("no session token; set AOC_SESSION"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! A rectangular grid of cells, for the puzzles whose input is a character
+//! map. Points are `(x, y)`, with `x` the column and `y` the row counting
+//! down from the top.
+
+use std::ops::Index;
+
+use crate::error::PuzzleError;
+use crate::parsing;
+
+pub type Point = (usize, usize);
+
+/// Steps to the four orthogonal neighbours: right, down, left and up.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Steps to all eight neighbours, diagonals included.
+pub const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// The cells row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` columns holding `cells` row by row.
+    ///
+    /// Panics unless `cells` fills a whole number of rows.
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "ragged grid"
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, mapping each character with `cell`. Every
+    /// line must be as long as the first, and `cell` must accept every
+    /// character.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, PuzzleError> {
+        parsing::parse_all(input, parsing::grid(cell))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// The point `step` away from `point`, if it is on the grid.
+    pub fn step(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The orthogonal neighbours of `point` on the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.step(point, d))
+    }
+
+    /// The neighbours of `point` on the grid, diagonals included.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ALL_AROUND.iter().filter_map(move |&d| self.step(point, d))
+    }
+
+    /// The cells of row `y`, left to right.
+    ///
+    /// Panics if `y` is not below the height.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells of column `x`, top to bottom.
+    ///
+    /// Panics if `x` is not below the width.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every point and its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    /// The points whose cells satisfy `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter_map(move |(point, cell)| predicate(cell).then_some(point))
+    }
+
+    /// The first point, row by row, whose cell satisfies `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.find_all(predicate).next()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The grid mirrored along its diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10))
+                .unwrap_err()
+                .to_string(),
+            "<input>:2:2: unexpected input (Char)"
+        );
+        assert_eq!(
+            Grid::parse("12\n345", |c| c.to_digit(10))
+                .unwrap_err()
+                .to_string(),
+            "<input>:2:1: unexpected input (Length followed by value)"
+        );
+        assert!(Grid::parse("", |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits("123\n456\n789");
+        let around = |point| {
+            let mut cells: Vec<_> = grid.neighbours8(point).map(|p| grid[p]).collect();
+            cells.sort();
+            cells
+        };
+        assert_eq!(around((0, 0)), [2, 4, 5]);
+        assert_eq!(around((1, 1)), [1, 2, 3, 4, 6, 7, 8, 9]);
+        let next: Vec<_> = grid.neighbours4((2, 1)).map(|p| grid[p]).collect();
+        assert_eq!(next, [9, 5, 3]);
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        let columns: Vec<Vec<u32>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.row(2), [3, 6]);
+        assert_eq!(
+            grid.find_all(|&d| d % 2 == 0).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1)]
+        );
+        assert_eq!(grid.find(|&d| d > 4), Some((1, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the grid")]
+    fn column_outside() {
+        // Without the check this would quietly yield a cell of the next row.
+        let _ = digits("123\n456").column(3);
+    }
+}
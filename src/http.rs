@@ -0,0 +1,360 @@
+//! Just enough HTTP/1.1 to talk to the puzzle site, over TLS for `https://`
+//! URLs and a plain TCP stream for `http://` ones, such as a local stand-in
+//! for the site.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+
+use crate::error::PuzzleError;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A response, with its body decoded as UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Where a URL points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Target<'a> {
+    tls: bool,
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+fn split_url(url: &str) -> io::Result<Target<'_>> {
+    let (tls, rest, default_port) = if let Some(rest) = url.strip_prefix("https://") {
+        (true, rest, 443)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (false, rest, 80)
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not an http:// or https:// URL",
+        ));
+    };
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "bad port"))?;
+            (host, port)
+        }
+        None => (authority, default_port),
+    };
+    if host.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no host"));
+    }
+    let path = if path.is_empty() { "/" } else { path };
+    Ok(Target {
+        tls,
+        host,
+        port,
+        path,
+    })
+}
+
+/// The `Cookie` header value for a session `token`, which must be visible
+/// ASCII, so that it cannot smuggle in other headers or cookies.
+pub fn session_cookie(token: &str) -> io::Result<String> {
+    if token.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the session token is empty",
+        ));
+    }
+    if !token.bytes().all(|b| b.is_ascii_graphic() && b != b';') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the session token must be visible ASCII without `;`",
+        ));
+    }
+    Ok(format!("session={token}"))
+}
+
+/// Sends one request to `url` and reads the whole response. Errors name the
+/// URL; a response with any status is `Ok`.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, PuzzleError> {
+    send(method, url, headers, body).map_err(|error| PuzzleError::Io {
+        file: url.to_string(),
+        error,
+    })
+}
+
+fn send(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let target = split_url(url)?;
+    let Target { host, path, .. } = target;
+    let mut request = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n\
+         User-Agent: my-project (Advent of Code 2023 solutions)\r\n"
+    );
+    for (name, value) in headers {
+        // A line break in a header would start a header of its own.
+        if [name, value]
+            .iter()
+            .any(|s| s.bytes().any(|b| b.is_ascii_control()))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("control characters in the {name} header"),
+            ));
+        }
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or_default());
+
+    let stream = TcpStream::connect((target.host, target.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let raw = if target.tls {
+        let name = ServerName::try_from(host.to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let connection = ClientConnection::new(tls_config(), name).map_err(io::Error::other)?;
+        exchange(StreamOwned::new(connection, stream), &request)?
+    } else {
+        exchange(stream, &request)?
+    };
+    parse_response(&raw)
+}
+
+/// Writes `request` and reads until the server closes the connection.
+fn exchange(mut stream: impl Read + Write, request: &str) -> io::Result<Vec<u8>> {
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+    let mut raw = Vec::new();
+    match stream.read_to_end(&mut raw) {
+        Ok(_) => Ok(raw),
+        // Plenty of servers close without a TLS close_notify; the length
+        // checks in `parse_response` still catch a truncated body.
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof && !raw.is_empty() => Ok(raw),
+        Err(e) => Err(e),
+    }
+}
+
+/// Trusts the Mozilla root certificates bundled by `webpki-roots`.
+fn tls_config() -> Arc<ClientConfig> {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    CONFIG
+        .get_or_init(|| {
+            let roots = RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            };
+            let provider = Arc::new(rustls::crypto::ring::default_provider());
+            let config = ClientConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default protocol versions")
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            Arc::new(config)
+        })
+        .clone()
+}
+
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+fn parse_response(raw: &[u8]) -> io::Result<Response> {
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| invalid("truncated response headers"))?;
+    let head = std::str::from_utf8(&raw[..split]).map_err(|_| invalid("headers are not UTF-8"))?;
+    let mut body = &raw[split + 4..];
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let mut fields = status_line.splitn(3, ' ');
+    let (Some(version), Some(status)) = (fields.next(), fields.next()) else {
+        return Err(invalid("malformed status line"));
+    };
+    if !version.starts_with("HTTP/") {
+        return Err(invalid("malformed status line"));
+    }
+    let status = status.parse().map_err(|_| invalid("malformed status"))?;
+    let reason = fields.next().unwrap_or_default().to_string();
+
+    let mut chunked = false;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("content-length") {
+            let length: usize = value.parse().map_err(|_| invalid("bad content length"))?;
+            body = body
+                .get(..length)
+                .ok_or_else(|| invalid("truncated response body"))?;
+        }
+    }
+    let body = if chunked {
+        dechunk(body)?
+    } else {
+        body.to_vec()
+    };
+    let body = String::from_utf8(body).map_err(|_| invalid("the body is not UTF-8"))?;
+    Ok(Response {
+        status,
+        reason,
+        body,
+    })
+}
+
+/// Joins the chunks of a `Transfer-Encoding: chunked` body.
+fn dechunk(mut raw: &[u8]) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let end = raw
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| invalid("truncated chunk"))?;
+        let size = std::str::from_utf8(&raw[..end])
+            .ok()
+            .and_then(|line| usize::from_str_radix(line.split(';').next()?.trim(), 16).ok())
+            .ok_or_else(|| invalid("bad chunk size"))?;
+        raw = &raw[end + 2..];
+        if size == 0 {
+            return Ok(body);
+        }
+        let chunk = raw.get(..size).ok_or_else(|| invalid("truncated chunk"))?;
+        body.extend_from_slice(chunk);
+        raw = raw
+            .get(size + 2..)
+            .ok_or_else(|| invalid("truncated chunk"))?;
+    }
+}
+
+/// A local stand-in for the puzzle site, for tests. It answers one
+/// connection per canned response, in order, then stops; joining the handle
+/// yields the requests it received.
+#[cfg(test)]
+pub(crate) fn stand_in(responses: Vec<String>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/2023", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            requests.push(request);
+            (&stream).write_all(response.as_bytes()).unwrap();
+        }
+        requests
+    });
+    (base_url, handle)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_response, session_cookie, split_url, Response, Target};
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            split_url("http://127.0.0.1:8080/2023/day/1").unwrap(),
+            Target {
+                tls: false,
+                host: "127.0.0.1",
+                port: 8080,
+                path: "/2023/day/1"
+            }
+        );
+        assert_eq!(
+            split_url("https://adventofcode.com").unwrap(),
+            Target {
+                tls: true,
+                host: "adventofcode.com",
+                port: 443,
+                path: "/"
+            }
+        );
+        assert!(split_url("ftp://example.com/").is_err());
+        assert!(split_url("http://:80/").is_err());
+    }
+
+    #[test]
+    fn session_cookies() {
+        assert_eq!(session_cookie("53616c7465").unwrap(), "session=53616c7465");
+        for token in [
+            "",
+            "cafe\r\nX-Evil: 1",
+            "ca fe",
+            "cafe;admin=1",
+            "caf\u{e9}",
+        ] {
+            assert!(session_cookie(token).is_err(), "{token:?}");
+        }
+        // Refused before connecting, so nothing needs to listen.
+        let error =
+            super::request("GET", "http://127.0.0.1:9/", &[("Cookie", "a\nb")], None).unwrap_err();
+        assert!(error.to_string().contains("control characters"), "{error}");
+    }
+
+    #[test]
+    fn responses() {
+        let plain = b"HTTP/1.1 404 Not Found\r\nContent-Length: 5\r\n\r\nhello, and more";
+        assert_eq!(
+            parse_response(plain).unwrap(),
+            Response {
+                status: 404,
+                reason: "Not Found".to_string(),
+                body: "hello".to_string(),
+            }
+        );
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                        4\r\n1 2 \r\n3;x=y\r\n3\n4\r\n0\r\n\r\n";
+        assert_eq!(parse_response(chunked).unwrap().body, "1 2 3\n4");
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 9\r\n\r\nshort").is_err());
+    }
+}
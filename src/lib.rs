@@ -0,0 +1,36 @@
+//! Advent of Code 2023 solvers.
+//!
+//! Each `dayN` module parses its puzzle input with `parse` and solves it with
+//! `part1` and `part2`, or straight from the text with `solve_part1` and
+//! `solve_part2`. [`solution::SOLUTIONS`] lists every day behind a common
+//! interface for tools that want to run them generically.
+
+pub mod answers;
+pub mod bench;
+pub mod bytes;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod http;
+pub mod log;
+pub mod math;
+pub mod parsing;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod vault;
+pub mod worker;
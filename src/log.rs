@@ -0,0 +1,157 @@
+//! Levelled diagnostics for the solvers, silent unless enabled through the
+//! `AOC_LOG` environment variable.
+//!
+//! `AOC_LOG` is a comma-separated list of directives. A bare level applies to
+//! every module and `module=level` to one module and its children, so
+//! `AOC_LOG=info,day12=trace` traces day 12 and reports only `info` and above
+//! elsewhere.
+
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn parse(name: &str) -> Option<Level> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Filter {
+    default: Option<Level>,
+    modules: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// Parses `AOC_LOG` directives, ignoring the ones it does not understand.
+    fn parse(spec: &str) -> Filter {
+        let mut filter = Filter::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    if let Some(level) = Level::parse(level) {
+                        filter.modules.push((module.to_string(), level));
+                    }
+                }
+                None => {
+                    if let Some(level) = Level::parse(directive) {
+                        filter.default = Some(level);
+                    }
+                }
+            }
+        }
+        filter
+    }
+
+    /// The most verbose level enabled for `module`, a path relative to the
+    /// crate root such as `day12`.
+    fn level(&self, module: &str) -> Option<Level> {
+        self.modules
+            .iter()
+            .filter(|(m, _)| {
+                module == m
+                    || module
+                        .strip_prefix(m.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(m, _)| m.len())
+            .map(|&(_, level)| level)
+            .or(self.default)
+    }
+}
+
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+    FILTER.get_or_init(|| Filter::parse(&env::var("AOC_LOG").unwrap_or_default()))
+}
+
+/// Strips the crate name from a `module_path!()`.
+fn relative(module_path: &str) -> &str {
+    module_path
+        .split_once("::")
+        .map_or(module_path, |(_, module)| module)
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    let filter = filter();
+    if filter.default.is_none() && filter.modules.is_empty() {
+        return false;
+    }
+    filter.level(relative(module_path)) >= Some(level)
+}
+
+pub fn write(level: Level, module_path: &str, message: fmt::Arguments) {
+    eprintln!("[{level:5} {}] {message}", relative(module_path));
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level, module_path!()) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log::log!($crate::log::Level::Debug, $($arg)+)
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log::log!($crate::log::Level::Trace, $($arg)+)
+    };
+}
+
+pub(crate) use {debug, log, trace};
+
+#[cfg(test)]
+mod test {
+    use super::{Filter, Level};
+
+    #[test]
+    fn silent_by_default() {
+        let filter = Filter::parse("");
+        assert_eq!(filter.level("day12"), None);
+    }
+
+    #[test]
+    fn per_module() {
+        let filter = Filter::parse("info, day12=trace,day1=nonsense");
+        assert_eq!(filter.level("day12"), Some(Level::Trace));
+        assert_eq!(filter.level("day12::test"), Some(Level::Trace));
+        assert_eq!(filter.level("day1"), Some(Level::Info));
+        assert_eq!(filter.level("day123"), Some(Level::Info));
+        assert!(filter.level("day5") < Some(Level::Debug));
+    }
+}
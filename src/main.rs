@@ -1,16 +1,712 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-
-fn main() {
-    println!("Hello, world!");
-}
\ No newline at end of file
+use std::env;
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+use my_project::answers::{self, ANSWERS_FILE, LOCAL_ANSWERS_FILE};
+use my_project::bench;
+use my_project::day1::{self, Vocabulary};
+use my_project::error::{with_input, PuzzleError};
+use my_project::examples::{self, GENERATED_FILE};
+use my_project::fetch::{self, Fetched, Fetcher};
+use my_project::scaffold;
+use my_project::solution::{self, default_input, Params, Part, SOLUTIONS};
+use my_project::submit::{self, Attempt, Submitter, Verdict, HISTORY_FILE};
+use my_project::vault::{self, Key};
+use my_project::worker::{self, Limits};
+
+const USAGE: &str = "usage: my-project run <day> <part> [--input PATH|-] [--<param> VALUE]...
+       my-project run --all [--<param> VALUE]...
+       my-project verify [--answers PATH] [--headers] [--reveal] [--allow-missing]
+       my-project hash-answers [--answers PATH]
+       my-project examples [--write]
+       my-project bench [<day> [<part>]] [--runs N] [--json] [--save PATH]
+                        [--baseline PATH] [--threshold PERCENT]
+       my-project fetch <day> [--dir PATH] [--base-url URL]
+       my-project submit <day> <part> [--input PATH] [--history PATH] [--base-url URL]
+       my-project vault seal|open [<day>...] [--remove]
+       my-project new <day>
+       my-project explain 1 <part> [--input PATH|-] [--words VOCABULARY] [--json]
+run, verify, bench and submit also take [--timeout SECONDS] [--stack-size MIB]
+for the thread each part is solved on, where a timeout of 0 waits for as long as
+it takes; bench allows each run the timeout. A part that times out is given up
+on, not stopped, and a stack overflow still ends the whole process.";
+
+enum Failure {
+    /// The command line was malformed.
+    Usage(String),
+    /// The command ran but could not produce an answer.
+    Run(String),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Usage(message)
+    }
+}
+
+impl From<&str> for Failure {
+    fn from(message: &str) -> Self {
+        Failure::Usage(message.to_string())
+    }
+}
+
+impl From<PuzzleError> for Failure {
+    fn from(error: PuzzleError) -> Self {
+        Failure::Run(error.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Failure::Usage(message)) => {
+            eprintln!("{message}\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(Failure::Run(message)) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Failure> {
+    match args.first().map(String::as_str) {
+        Some("run") => solve(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("hash-answers") => hash_answers(&args[1..]),
+        Some("examples") => extract_examples(&args[1..]),
+        Some("bench") => benchmark(&args[1..]),
+        Some("fetch") => fetch_input(&args[1..]),
+        Some("submit") => submit_answer(&args[1..]),
+        Some("vault") => vault_inputs(&args[1..]),
+        Some("new") => new_day(&args[1..]),
+        Some("explain") => explain(&args[1..]),
+        Some(command) => Err(Failure::Usage(format!("unknown command `{command}`"))),
+        None => Err("missing command".into()),
+    }
+}
+
+fn solve(args: &[String]) -> Result<(), Failure> {
+    let mut all = false;
+    let mut input = None;
+    let mut params = Params::default();
+    let mut param_names = Vec::new();
+    let mut limits = Limits::default();
+    let mut positional = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--input" => input = Some(value(rest.next(), "--input")?.to_string()),
+            "--timeout" => limits.timeout = timeout(value(rest.next(), "--timeout")?)?,
+            "--stack-size" => limits.stack_size = stack_size(value(rest.next(), "--stack-size")?)?,
+            flag if flag.starts_with("--") => {
+                let name = &flag[2..];
+                if !SOLUTIONS.iter().any(|s| s.params().contains(&name)) {
+                    return Err(Failure::Usage(format!("unknown flag `{flag}`")));
+                }
+                params.set(name, value(rest.next(), flag)?);
+                param_names.push(name);
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    if all {
+        if !positional.is_empty() || input.is_some() {
+            return Err("`run --all` takes no day, part or input".into());
+        }
+        for solution in SOLUTIONS {
+            let day = solution.day();
+            let input = default_input(day);
+            if !vault::available(&input) {
+                eprintln!("day {day}: skipped, no input at {input}");
+                continue;
+            }
+            // Each part gets a worker, so a hang or a panic costs only that
+            // part.
+            let solved = vault::with_input(&input, |text| {
+                for &part in solution.parts() {
+                    let params = params.clone();
+                    match worker::solve(*solution, part, text.to_string(), params, &limits) {
+                        Ok(answer) => println!("day {day} part {part}: {answer}"),
+                        Err(e) => eprintln!("day {day} part {part}: {}", e.in_file(&input)),
+                    }
+                }
+                Ok(())
+            });
+            if let Err(e) = solved {
+                eprintln!("day {day}: {e}");
+            }
+        }
+        return Ok(());
+    }
+
+    let [day, part] = positional[..] else {
+        return Err("expected a day and a part".into());
+    };
+    let day: u8 = parse(day)?;
+    let solution = solution::find(day).ok_or_else(|| format!("day {day} is not implemented"))?;
+    let part = Part::from_number(parse(part)?).ok_or("the part must be 1 or 2")?;
+    if !solution.parts().contains(&part) {
+        return Err(Failure::Run(format!(
+            "day {day} part {part} is not implemented"
+        )));
+    }
+    if let Some(name) = param_names.iter().find(|n| !solution.params().contains(n)) {
+        return Err(Failure::Usage(format!("day {day} takes no `--{name}`")));
+    }
+    let input = input.unwrap_or_else(|| default_input(day));
+    let answer = if input == "-" && day == 1 {
+        // Day 1 reads a line at a time, so it need not hold all of stdin.
+        worker::run(&format!("day 1 part {part}"), &limits, move || {
+            let reader = BufReader::new(io::stdin());
+            day1::solve_reader(reader, "<stdin>", part, &params)
+        })?
+    } else if input == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| Failure::Run(format!("<stdin>: {e}")))?;
+        worker::solve(solution, part, text, params, &limits)?
+    } else if vault::exists(&input) {
+        worker::solve_file(solution, part, &input, &params, &limits)?
+    } else {
+        return Err(Failure::Run(format!("no input at {input}")));
+    };
+    println!("{answer}");
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<(), Failure> {
+    let mut file = ANSWERS_FILE.to_string();
+    let mut headers = false;
+    let mut reveal = false;
+    let mut allow_missing = false;
+    let mut limits = Limits::default();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--answers" => file = value(rest.next(), "--answers")?.to_string(),
+            "--timeout" => limits.timeout = timeout(value(rest.next(), "--timeout")?)?,
+            "--stack-size" => limits.stack_size = stack_size(value(rest.next(), "--stack-size")?)?,
+            "--headers" => headers = true,
+            "--reveal" => reveal = true,
+            "--allow-missing" => allow_missing = true,
+            _ => return Err(Failure::Usage(format!("unexpected argument `{arg}`"))),
+        }
+    }
+    let mut known = with_input(&file, answers::parse)?;
+    if reveal {
+        if Path::new(LOCAL_ANSWERS_FILE).exists() {
+            let local = with_input(LOCAL_ANSWERS_FILE, answers::parse)?;
+            answers::reveal(&mut known, &local);
+        } else {
+            eprintln!("no {LOCAL_ANSWERS_FILE}, so hashed answers stay hidden");
+        }
+    }
+    let mut drifted = 0;
+    let checks = if headers {
+        let recorded = answers::from_headers()?;
+        for (ours, theirs) in answers::drift(&known, &recorded) {
+            drifted += 1;
+            eprintln!(
+                "day {} part {}: {file} has {} but {} records {}",
+                ours.day,
+                ours.part,
+                ours.answer,
+                answers::source_file(ours.day),
+                theirs.answer
+            );
+        }
+        answers::verify(&recorded, &limits)
+    } else {
+        answers::verify(&known, &limits)
+    };
+    print!("{}", answers::table(&checks));
+    let missing = checks.iter().filter(|c| c.skipped()).count();
+    let failed = checks
+        .iter()
+        .filter(|c| !c.passed() && !c.skipped())
+        .count();
+    if failed > 0 {
+        return Err(Failure::Run(format!(
+            "{failed} of {} answers did not match",
+            checks.len()
+        )));
+    }
+    if missing > 0 && !allow_missing {
+        return Err(Failure::Run(format!(
+            "{missing} of {} answers had no input to check; \
+             pass --allow-missing to accept that",
+            checks.len()
+        )));
+    }
+    if drifted > 0 {
+        return Err(Failure::Run(format!(
+            "{drifted} answers differ between {file} and the headers"
+        )));
+    }
+    Ok(())
+}
+
+fn hash_answers(args: &[String]) -> Result<(), Failure> {
+    let file = match args {
+        [] => ANSWERS_FILE,
+        [flag, path] if flag == "--answers" => path.as_str(),
+        _ => return Err("`hash-answers` takes only `--answers PATH`".into()),
+    };
+    let (hashed, plain) = with_input(file, |text| {
+        answers::hash_all(text, |_| answers::fresh_salt())
+    })?;
+    if plain.is_empty() {
+        println!("every answer in {file} is already hashed");
+        return Ok(());
+    }
+
+    // Save the plain answers before they disappear from the answers file.
+    let mut local = if Path::new(LOCAL_ANSWERS_FILE).exists() {
+        with_input(LOCAL_ANSWERS_FILE, answers::parse)?
+    } else {
+        Vec::new()
+    };
+    local.retain(|l| {
+        !plain
+            .iter()
+            .any(|p| (p.day, p.part, &p.input) == (l.day, l.part, &l.input))
+    });
+    local.extend(plain.iter().cloned());
+    fs::write(LOCAL_ANSWERS_FILE, answers::render(&local))
+        .map_err(|e| Failure::Run(format!("{LOCAL_ANSWERS_FILE}: {e}")))?;
+    fs::write(file, hashed).map_err(|e| Failure::Run(format!("{file}: {e}")))?;
+    println!(
+        "hashed {} answers in {file}; the plain ones are in {LOCAL_ANSWERS_FILE}",
+        plain.len()
+    );
+    Ok(())
+}
+
+fn extract_examples(args: &[String]) -> Result<(), Failure> {
+    let write = match args {
+        [] => false,
+        [flag] if flag == "--write" => true,
+        _ => return Err("`examples` takes only `--write`".into()),
+    };
+    let examples = examples::extract_all()?;
+    if write {
+        fs::write(GENERATED_FILE, examples::generate(&examples))
+            .map_err(|e| Failure::Run(format!("{GENERATED_FILE}: {e}")))?;
+        println!("wrote {} examples to {GENERATED_FILE}", examples.len());
+    } else {
+        for example in &examples {
+            let lines = example.input.lines().count();
+            println!(
+                "day {} part {}: {} ({lines} lines of input)",
+                example.day, example.part, example.answer
+            );
+        }
+    }
+    Ok(())
+}
+
+fn benchmark(args: &[String]) -> Result<(), Failure> {
+    let mut runs = 10;
+    let mut json = false;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut limits = Limits::default();
+    let mut positional = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--runs" => runs = parse(value(rest.next(), "--runs")?)?,
+            "--timeout" => limits.timeout = timeout(value(rest.next(), "--timeout")?)?,
+            "--stack-size" => limits.stack_size = stack_size(value(rest.next(), "--stack-size")?)?,
+            "--json" => json = true,
+            "--save" => save = Some(value(rest.next(), "--save")?.to_string()),
+            "--baseline" => baseline = Some(value(rest.next(), "--baseline")?.to_string()),
+            "--threshold" => threshold = parse(value(rest.next(), "--threshold")?)?,
+            flag if flag.starts_with("--") => {
+                return Err(Failure::Usage(format!("unknown flag `{flag}`")))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    if runs == 0 {
+        return Err("`--runs` must be at least 1".into());
+    }
+    let runs_u32 = u32::try_from(runs).unwrap_or(u32::MAX);
+    limits.timeout = limits.timeout.map(|t| t.saturating_mul(runs_u32));
+    let (day, part) = match positional[..] {
+        [] => (None, None),
+        [day] => (Some(parse(day)?), None),
+        [day, part] => {
+            let part = Part::from_number(parse(part)?).ok_or("the part must be 1 or 2")?;
+            (Some(parse(day)?), Some(part))
+        }
+        _ => return Err("expected at most a day and a part".into()),
+    };
+    let solutions: Vec<_> = match day {
+        Some(day) => {
+            vec![solution::find(day).ok_or_else(|| format!("day {day} is not implemented"))?]
+        }
+        None => SOLUTIONS.to_vec(),
+    };
+
+    let mut timings = Vec::new();
+    let mut failed = Vec::new();
+    for solution in solutions {
+        let day = solution.day();
+        let input = default_input(day);
+        if !vault::available(&input) {
+            eprintln!("day {day}: skipped, no input at {input}");
+            continue;
+        }
+        let parts = solution
+            .parts()
+            .iter()
+            .filter(|&&p| part.is_none_or(|part| p == part));
+        for &part in parts {
+            let timing = vault::with_input(&input, |text| {
+                let text = text.to_string();
+                worker::run(&format!("day {day} part {part}"), &limits, move || {
+                    bench::time(solution, part, &text, &Params::default(), runs)
+                })
+            });
+            match timing {
+                Ok(timing) => timings.push(timing),
+                Err(e) => {
+                    eprintln!("day {day} part {part}: {e}");
+                    failed.push(bench::Failed {
+                        day,
+                        part,
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    if json {
+        print!("{}", bench::json(&timings, &failed));
+    } else {
+        print!("{}", bench::table(&timings, &failed));
+    }
+    if let Some(file) = save {
+        fs::write(&file, bench::save(&timings))
+            .map_err(|e| Failure::Run(format!("{file}: {e}")))?;
+        eprintln!("saved the medians of {} parts to {file}", timings.len());
+    }
+    if let Some(file) = baseline {
+        let baseline = with_input(&file, bench::parse_baseline)?;
+        let regressions = bench::regressions(&timings, &baseline, threshold);
+        for r in &regressions {
+            eprintln!(
+                "day {} part {} {}: {:.1?} -> {:.1?}",
+                r.day, r.part, r.phase, r.before, r.after
+            );
+        }
+        if !regressions.is_empty() {
+            return Err(Failure::Run(format!(
+                "{} phases regressed by more than {threshold}% against {file}",
+                regressions.len()
+            )));
+        }
+    }
+    if !failed.is_empty() {
+        return Err(Failure::Run(format!("{} parts failed", failed.len())));
+    }
+    Ok(())
+}
+
+fn fetch_input(args: &[String]) -> Result<(), Failure> {
+    let mut dir = PathBuf::from(fetch::DEFAULT_DIR);
+    let mut base_url =
+        env::var(fetch::BASE_URL_VAR).unwrap_or_else(|_| fetch::DEFAULT_BASE_URL.to_string());
+    let mut day = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--dir" => dir = PathBuf::from(value(rest.next(), "--dir")?),
+            "--base-url" => base_url = value(rest.next(), "--base-url")?.to_string(),
+            flag if flag.starts_with("--") => {
+                return Err(Failure::Usage(format!("unknown flag `{flag}`")))
+            }
+            _ if day.is_none() => day = Some(parse::<u8>(arg)?),
+            _ => return Err(Failure::Usage(format!("unexpected argument `{arg}`"))),
+        }
+    }
+    let day = day.ok_or("expected a day")?;
+    if !(1..=25).contains(&day) {
+        return Err("the day must be between 1 and 25".into());
+    }
+    let fetcher = Fetcher {
+        base_url,
+        session: env::var(fetch::SESSION_VAR)
+            .ok()
+            .map(|s| s.trim().to_string()),
+        dir,
+    };
+    match fetcher.fetch(day)? {
+        Fetched::Cached(path) => println!("day {day}: already cached at {}", path.display()),
+        Fetched::Downloaded(path) => println!("day {day}: downloaded to {}", path.display()),
+    }
+    Ok(())
+}
+
+fn submit_answer(args: &[String]) -> Result<(), Failure> {
+    let mut input = None;
+    let mut history_file = HISTORY_FILE.to_string();
+    let mut base_url =
+        env::var(fetch::BASE_URL_VAR).unwrap_or_else(|_| fetch::DEFAULT_BASE_URL.to_string());
+    let mut limits = Limits::default();
+    let mut positional = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--input" => input = Some(value(rest.next(), "--input")?.to_string()),
+            "--timeout" => limits.timeout = timeout(value(rest.next(), "--timeout")?)?,
+            "--stack-size" => limits.stack_size = stack_size(value(rest.next(), "--stack-size")?)?,
+            "--history" => history_file = value(rest.next(), "--history")?.to_string(),
+            "--base-url" => base_url = value(rest.next(), "--base-url")?.to_string(),
+            flag if flag.starts_with("--") => {
+                return Err(Failure::Usage(format!("unknown flag `{flag}`")))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    let [day, part] = positional[..] else {
+        return Err("expected a day and a part".into());
+    };
+    let day: u8 = parse(day)?;
+    let solution = solution::find(day).ok_or_else(|| format!("day {day} is not implemented"))?;
+    let part = Part::from_number(parse(part)?).ok_or("the part must be 1 or 2")?;
+    if !solution.parts().contains(&part) {
+        return Err(Failure::Run(format!(
+            "day {day} part {part} is not implemented"
+        )));
+    }
+
+    let input = input.unwrap_or_else(|| default_input(day));
+    let answer = worker::solve_file(solution, part, &input, &Params::default(), &limits)?;
+    let history = if Path::new(&history_file).exists() {
+        with_input(&history_file, submit::parse_history)?
+    } else {
+        Vec::new()
+    };
+    submit::vet(&history, day, part, answer, SystemTime::now())
+        .map_err(|refusal| Failure::Run(format!("not submitting {answer}: {refusal}")))?;
+
+    let session = env::var(fetch::SESSION_VAR)
+        .map_err(|_| Failure::Run(format!("no session token; set {}", fetch::SESSION_VAR)))?;
+    let submitter = Submitter {
+        base_url,
+        session: session.trim().to_string(),
+    };
+    let at = SystemTime::now();
+    let verdict = submitter.submit(day, part, answer)?;
+    submit::record(
+        &history_file,
+        &Attempt {
+            day,
+            part,
+            answer,
+            verdict,
+            at,
+        },
+    )?;
+    println!("day {day} part {part}: {answer} is {verdict}");
+    if verdict == Verdict::Correct {
+        Ok(())
+    } else {
+        Err(Failure::Run(format!("{answer} was not accepted")))
+    }
+}
+
+fn vault_inputs(args: &[String]) -> Result<(), Failure> {
+    let sealing = match args.first().map(String::as_str) {
+        Some("seal") => true,
+        Some("open") => false,
+        _ => return Err("expected `vault seal` or `vault open`".into()),
+    };
+    let mut remove = false;
+    let mut days = Vec::new();
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--remove" => remove = true,
+            flag if flag.starts_with("--") => {
+                return Err(Failure::Usage(format!("unknown flag `{flag}`")))
+            }
+            day => days.push(parse::<u8>(day)?),
+        }
+    }
+    if days.is_empty() {
+        days = SOLUTIONS.iter().map(|s| s.day()).collect();
+    }
+    let key = Key::from_env()
+        .ok_or_else(|| Failure::Run(format!("no vault key; set {}", vault::KEY_VAR)))?;
+
+    for day in days {
+        let plain = default_input(day);
+        let sealed = vault::sealed_path(&plain);
+        let (from, to) = if sealing {
+            (&plain, &sealed)
+        } else {
+            (&sealed, &plain)
+        };
+        if !Path::new(from).exists() {
+            eprintln!("day {day}: skipped, no {from}");
+            continue;
+        }
+        let text = with_input(from, |text| Ok(text.to_string()))?;
+        // Every seal comes out different, so an input sealed already is left
+        // alone rather than giving git a new file to commit.
+        let unchanged = sealing
+            && Path::new(to).exists()
+            && with_input(to, |sealed| vault::open(&key, sealed)).is_ok_and(|old| old == text);
+        if unchanged {
+            println!("day {day}: {to} is up to date");
+        } else {
+            let text = if sealing {
+                vault::seal(&key, &text)?
+            } else {
+                vault::open(&key, &text)?
+            };
+            fs::write(to, text).map_err(|e| Failure::Run(format!("{to}: {e}")))?;
+            println!("day {day}: wrote {to}");
+        }
+        if remove {
+            fs::remove_file(from).map_err(|e| Failure::Run(format!("{from}: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+fn new_day(args: &[String]) -> Result<(), Failure> {
+    let [day] = args else {
+        return Err("expected a day".into());
+    };
+    let day: u8 = parse(day)?;
+    if !(1..=25).contains(&day) {
+        return Err("the day must be between 1 and 25".into());
+    }
+    let module = format!("src/day{day}.rs");
+    if solution::find(day).is_some() || Path::new(&module).exists() {
+        return Err(Failure::Run(format!("day {day} already exists")));
+    }
+
+    // Work out every edit before writing any, so a failure leaves no half
+    // registered day behind.
+    let lib = with_input("src/lib.rs", |text| scaffold::register_module(text, day))?;
+    let registry = with_input("src/solution.rs", |text| {
+        scaffold::register_solution(text, day)
+    })?;
+    let write = |file: &str, text: &str| {
+        fs::write(file, text).map_err(|e| Failure::Run(format!("{file}: {e}")))
+    };
+    write(&module, &scaffold::module(day))?;
+    write("src/lib.rs", &lib)?;
+    write("src/solution.rs", &registry)?;
+    println!("created {module} and registered day {day}");
+    let input = default_input(day);
+    if !vault::exists(&input) {
+        write(&input, "")?;
+        println!("created an empty {input}; `my-project fetch {day}` fills it in");
+    }
+    Ok(())
+}
+
+fn explain(args: &[String]) -> Result<(), Failure> {
+    let mut input = None;
+    let mut words = None;
+    let mut json = false;
+    let mut positional = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--input" => input = Some(value(rest.next(), "--input")?.to_string()),
+            "--words" => words = Some(value(rest.next(), "--words")?.to_string()),
+            "--json" => json = true,
+            flag if flag.starts_with("--") => {
+                return Err(Failure::Usage(format!("unknown flag `{flag}`")))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    let [day, part] = positional[..] else {
+        return Err("expected a day and a part".into());
+    };
+    let day: u8 = parse(day)?;
+    if day != 1 {
+        return Err(Failure::Run(format!("day {day} has no explain report")));
+    }
+    let part = Part::from_number(parse(part)?).ok_or("the part must be 1 or 2")?;
+    let vocabulary = match (part, words) {
+        (Part::One, Some(_)) => return Err("part 1 reads no words".into()),
+        (Part::One, None) => Vocabulary::digits(),
+        (Part::Two, Some(words)) => words.parse()?,
+        (Part::Two, None) => Vocabulary::english(),
+    };
+
+    let report = |text: &str| {
+        let explanations = day1::explain(&day1::parse(text)?, &vocabulary);
+        if json {
+            print!("{}", day1::explain_json(&explanations));
+        } else {
+            print!("{}", day1::explain_table(&explanations));
+        }
+        let missing = explanations.iter().filter(|e| e.value().is_none()).count();
+        Ok((missing, explanations.len()))
+    };
+    let input = input.unwrap_or_else(|| default_input(day));
+    let (missing, lines) = if input == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| Failure::Run(format!("<stdin>: {e}")))?;
+        report(&text)?
+    } else {
+        vault::with_input(&input, report)?
+    };
+    if missing > 0 {
+        return Err(Failure::Run(format!(
+            "no digits on {missing} of {lines} lines"
+        )));
+    }
+    Ok(())
+}
+
+fn value<'a>(value: Option<&'a String>, flag: &str) -> Result<&'a str, String> {
+    value
+        .map(String::as_str)
+        .ok_or_else(|| format!("`{flag}` needs a value"))
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a valid number"))
+}
+
+/// A `--timeout` in seconds, where 0 means none.
+fn timeout(value: &str) -> Result<Option<Duration>, String> {
+    let seconds: f64 = parse(value)?;
+    let timeout = Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("`{value}` is not a usable number of seconds"))?;
+    Ok((seconds > 0.0).then_some(timeout))
+}
+
+/// A `--stack-size` in MiB.
+fn stack_size(value: &str) -> Result<usize, String> {
+    let mib: usize = parse(value)?;
+    match mib.checked_mul(1 << 20) {
+        Some(bytes) if bytes > 0 => Ok(bytes),
+        _ => Err(format!("`{value}` MiB is not a usable stack size")),
+    }
+}
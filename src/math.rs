@@ -0,0 +1,203 @@
+//! Number theory shared between days: gcd and lcm that report overflow rather
+//! than wrapping, extended Euclid, the Chinese remainder theorem, integer
+//! square roots and modular exponentiation.
+
+use std::fmt::Debug;
+
+/// The unsigned integer types the gcd family works over.
+pub trait Unsigned: Copy + Eq + Ord + Debug {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn rem(self, other: Self) -> Self;
+    fn div(self, other: Self) -> Self;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn rem(self, other: Self) -> Self {
+                self % other
+            }
+
+            fn div(self, other: Self) -> Self {
+                self / other
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+unsigned!(u64, u128);
+
+/// Greatest common divisor, with `gcd(0, 0) == 0`.
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a.rem(b));
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it does not fit in `T`.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    a.div(gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of all of `nums`, 1 for none, or `None` if it does
+/// not fit in `T`.
+pub fn lcm_all<T: Unsigned>(nums: &[T]) -> Option<T> {
+    nums.iter().try_fold(T::ONE, |acc, &n| lcm(acc, n))
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, where `g` is the
+/// non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every pair in `congruences`, whose
+/// moduli need not be coprime. Returns `(x, m)` with `0 <= x < m`, where
+/// every solution is `x` plus a multiple of `m`, or `None` if a modulus is
+/// not positive, the congruences contradict each other or `m` overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(a1, m1): (i128, i128), &(a2, m2)| {
+            if m2 <= 0 {
+                return None;
+            }
+            let (g, p, _) = extended_gcd(m1, m2);
+            let diff = a2.checked_sub(a1)?;
+            if diff % g != 0 {
+                return None;
+            }
+            // m1 * p ≡ g (mod m2), so stepping by m1 * k with
+            // k ≡ diff / g * p (mod m2 / g) lands on a2.
+            let step = m2 / g;
+            let k = (diff / g)
+                .rem_euclid(step)
+                .checked_mul(p.rem_euclid(step))?
+                .rem_euclid(step);
+            let m = m1.checked_mul(step)?;
+            let x = a1.checked_add(m1.checked_mul(k)?)?.rem_euclid(m);
+            Some((x, m))
+        })
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above: start at a power of two past the root.
+    let mut x = 1 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// `base` raised to `exp`, modulo `modulus`, or `None` for a zero modulus.
+pub fn modpow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(super::gcd(12u64, 18), 6);
+        assert_eq!(super::gcd(0u64, 7), 7);
+        assert_eq!(super::gcd(0u128, 0), 0);
+        assert_eq!(super::lcm(4u64, 6), Some(12));
+        assert_eq!(super::lcm_all::<u64>(&[]), Some(1));
+        assert_eq!(super::lcm_all(&[2u64, 3, 4, 5]), Some(60));
+        assert_eq!(super::lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(
+            super::lcm(u64::MAX as u128, u64::MAX as u128 - 1),
+            Some(u64::MAX as u128 * (u64::MAX as u128 - 1))
+        );
+    }
+
+    #[test]
+    fn extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (0, 5), (7, 0), (17, 5)] {
+            let (g, x, y) = super::extended_gcd(a, b);
+            assert_eq!(g, super::gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn crt() {
+        assert_eq!(super::crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(super::crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(super::crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(super::crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(super::crt(&[]), Some((0, 1)));
+        assert_eq!(super::crt(&[(1, 3), (0, 0)]), None);
+        assert_eq!(super::crt(&[(1, -3)]), None);
+        assert_eq!(super::crt(&[(0, i128::MAX), (1, i128::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn isqrt() {
+        for n in 0..1000 {
+            let r = super::isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(super::isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(super::isqrt((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn modpow() {
+        assert_eq!(super::modpow(4, 13, 497), Some(445));
+        assert_eq!(super::modpow(2, 0, 1), Some(0));
+        assert_eq!(super::modpow(2, 3, 0), None);
+        assert_eq!(super::modpow(u64::MAX, u64::MAX, u64::MAX - 58), {
+            // Fermat: u64::MAX - 58 is prime.
+            let p = u64::MAX - 58;
+            super::modpow(u64::MAX % p, u64::MAX % (p - 1), p)
+        });
+    }
+}
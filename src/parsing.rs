@@ -0,0 +1,184 @@
+//! nom combinators for the input shapes the puzzles keep coming back to, and
+//! drivers that run them over a whole input and report failures as
+//! [`PuzzleError`]s pointing into it.
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{
+    alphanumeric1, char, i64, line_ending, multispace0, space0, space1, u64,
+};
+use nom::combinator::all_consuming;
+use nom::error::{Error, ErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated};
+use nom::{IResult, Parser};
+
+use crate::error::PuzzleError;
+use crate::grid::Grid;
+
+/// Unsigned numbers separated by spaces, as in `41 48  83 86`.
+pub fn numbers(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(space1, u64)(input)
+}
+
+/// Possibly negative numbers separated by spaces, as in `10 -3 0`.
+pub fn signed_numbers(input: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(space1, i64)(input)
+}
+
+/// `label` and any spaces after it, then `parser`, as in `seeds: 79 14`
+/// or `Time:      7  15`.
+pub fn labeled<'a, O>(
+    label: &'static str,
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(pair(tag(label), space0), parser)
+}
+
+/// The number in a `label N:` prefix such as `Card  12: `, spaces included.
+pub fn labeled_id<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, u64> {
+    delimited(pair(tag(label), space1), u64, pair(char(':'), space0))
+}
+
+/// A `key = (left, right)` line, as in day 8's network.
+pub fn key_pair(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tag(" = "),
+        delimited(
+            char('('),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            char(')'),
+        ),
+    )(input)
+}
+
+/// `parser` repeated on consecutive lines.
+pub fn lines_of<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, parser)
+}
+
+/// Sections separated by blank lines, each parsed by `section`.
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(line_ending, line_ending), section)
+}
+
+/// A rectangular map with one character per cell, mapped by `cell`. A
+/// character `cell` rejects fails with `ErrorKind::Char`, and a row of
+/// a different width from the first with `ErrorKind::LengthValue`.
+pub fn grid<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input: &'a str| {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut rest = input;
+        loop {
+            let end = rest.find(['\r', '\n']).unwrap_or(rest.len());
+            let line = &rest[..end];
+            if line.is_empty() {
+                break;
+            }
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let tile = cell(c)
+                    .ok_or_else(|| nom::Err::Error(Error::new(&line[i..], ErrorKind::Char)))?;
+                cells.push(tile);
+            }
+            let row = cells.len() - before;
+            if *width.get_or_insert(row) != row {
+                return Err(nom::Err::Failure(Error::new(line, ErrorKind::LengthValue)));
+            }
+            rest = &rest[end..];
+            match line_ending::<_, Error<_>>(rest) {
+                Ok((next, _)) if !next.starts_with(['\r', '\n']) && !next.is_empty() => rest = next,
+                _ => break,
+            }
+        }
+        match width {
+            Some(width) => Ok((rest, Grid::new(width, cells))),
+            None => Err(nom::Err::Error(Error::new(input, ErrorKind::Eof))),
+        }
+    }
+}
+
+/// Runs `parser` over all of `input`, allowing trailing whitespace.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<O, PuzzleError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|e| PuzzleError::from_nom(input, e))
+}
+
+/// Runs `parser` over each line of `input`, which it must consume whole.
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<Vec<O>, PuzzleError> {
+    input
+        .lines()
+        .map(|line| {
+            all_consuming(|line| parser.parse(line))(line)
+                .map(|(_, output)| output)
+                .map_err(|e| PuzzleError::from_nom(input, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use nom::character::complete::u64;
+    use nom::sequence::separated_pair;
+
+    use super::{grid, key_pair, labeled, labeled_id, lines_of, numbers, sections, signed_numbers};
+    use super::{parse_all, parse_lines};
+
+    #[test]
+    fn numbers_and_labels() {
+        assert_eq!(numbers("41 48  83 |"), Ok((" |", vec![41, 48, 83])));
+        assert_eq!(signed_numbers("10 -3 0"), Ok(("", vec![10, -3, 0])));
+        assert_eq!(
+            labeled("Time:", numbers)("Time:      7  15"),
+            Ok(("", vec![7, 15]))
+        );
+        assert_eq!(labeled_id("Card")("Card  12: 1 2"), Ok(("1 2", 12)));
+        assert_eq!(
+            key_pair("11A = (11B, XXX)"),
+            Ok(("", ("11A", ("11B", "XXX"))))
+        );
+    }
+
+    #[test]
+    fn sections_of_lines() {
+        let input = "1 2\n3 4\n\n5 6\n";
+        let pairs = || lines_of(separated_pair(u64, nom::character::complete::space1, u64));
+        assert_eq!(
+            parse_all(input, sections(pairs())).unwrap(),
+            [vec![(1, 2), (3, 4)], vec![(5, 6)]]
+        );
+        assert_eq!(
+            parse_lines("1 2\n3 x", numbers).unwrap_err().to_string(),
+            "<input>:2:2: unexpected input (End of file)"
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let digits = || grid(|c| c.to_digit(10));
+        let (rest, parsed) = digits()("12\n34\n\nnext").unwrap();
+        assert_eq!((parsed.width(), parsed.height(), rest), (2, 2, "\n\nnext"));
+        assert_eq!(
+            parse_all("12\n3x", digits()).unwrap_err().to_string(),
+            "<input>:2:2: unexpected input (Char)"
+        );
+        assert_eq!(
+            parse_all("12\n345", digits()).unwrap_err().to_string(),
+            "<input>:2:1: unexpected input (Length followed by value)"
+        );
+    }
+}
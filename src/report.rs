@@ -0,0 +1,87 @@
+//! Laying results out for people, as aligned tables, and for tools, as JSON.
+
+/// How a table column lines its cells up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Lays `rows` out under `header`, each column as wide as its widest cell
+/// and two spaces from the next.
+pub fn table<const N: usize>(header: [&str; N], align: [Align; N], rows: &[[String; N]]) -> String {
+    let header = header.map(String::from);
+    let mut widths = [0; N];
+    for row in std::iter::once(&header).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter().zip(align))
+            .map(|(cell, (&width, align))| match align {
+                Align::Left => format!("{cell:width$}"),
+                Align::Right => format!("{cell:>width$}"),
+            })
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// `text` as a JSON string literal.
+pub fn json_string(text: &str) -> String {
+    let mut out = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The JSON `entries` as an array, one to a line.
+pub fn json_array(entries: &[String]) -> String {
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Align;
+
+    #[test]
+    fn table() {
+        let rows = [
+            ["1".to_string(), "one".to_string(), "x".to_string()],
+            ["10".to_string(), "ten".to_string(), String::new()],
+        ];
+        assert_eq!(
+            super::table(
+                ["n", "word", "note"],
+                [Align::Right, Align::Left, Align::Left],
+                &rows
+            ),
+            " n  word  note\n 1  one   x\n10  ten\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            super::json_string("a \"b\"\\\n"),
+            "\"a \\\"b\\\"\\\\\\u000a\""
+        );
+        assert_eq!(
+            super::json_array(&["1".to_string(), "2".to_string()]),
+            "[\n1,\n2\n]\n"
+        );
+    }
+}
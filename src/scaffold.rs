@@ -0,0 +1,219 @@
+//! Generating the skeleton of a new day: its module, its place in `lib.rs`
+//! and the registry, and an empty input to fill in.
+
+use crate::error::PuzzleError;
+
+const TEMPLATE: &str = r#"/*
+--- Day DAY: ? ---
+Paste the puzzle text here for `my-project examples --write` to read,
+leaving out your answers: answers.txt keeps them, hashed.
+*/
+
+use crate::error::PuzzleError;
+use crate::solution::{Answer, Params, Parsed, Part, Solution};
+
+pub struct Puzzle<'a> {
+    pub lines: Vec<&'a str>,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle<'_>, PuzzleError> {
+    Ok(Puzzle {
+        lines: input.lines().collect(),
+    })
+}
+
+pub fn part1(_puzzle: &Puzzle) -> Result<u64, PuzzleError> {
+    Err(PuzzleError::invalid("not solved yet"))
+}
+
+pub fn part2(_puzzle: &Puzzle) -> Result<u64, PuzzleError> {
+    Err(PuzzleError::invalid("not solved yet"))
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, PuzzleError> {
+    part1(&parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, PuzzleError> {
+    part2(&parse(input)?)
+}
+
+pub struct DayDAY;
+
+impl Solution for DayDAY {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    /// Each part listed here needs its answer in `answers.txt`.
+    fn parts(&self) -> &'static [Part] {
+        &[]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError> {
+        Ok(Box::new(parse(input)?))
+    }
+}
+
+impl Parsed for Puzzle<'_> {
+    fn solve(&self, part: Part, _: &Params) -> Result<Answer, PuzzleError> {
+        Ok(match part {
+            Part::One => part1(self)?.into(),
+            Part::Two => part2(self)?.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "needs the example and its answer"]
+    fn part1_example() {
+        assert_eq!(super::solve_part1(EXAMPLE).unwrap(), 0)
+    }
+    #[test]
+    #[ignore = "needs the example and its answer"]
+    fn part2_example() {
+        assert_eq!(super::solve_part2(EXAMPLE).unwrap(), 0)
+    }
+}
+"#;
+
+/// The source of a new `dayN.rs`.
+pub fn module(day: u8) -> String {
+    TEMPLATE.replace("DAY", &day.to_string())
+}
+
+/// `lib.rs` with `pub mod dayN;` added among the other days, in the order
+/// rustfmt keeps them.
+pub fn register_module(lib: &str, day: u8) -> Result<String, PuzzleError> {
+    let line = format!("pub mod day{day};");
+    let days: Vec<&str> = lib
+        .lines()
+        .filter(|l| l.starts_with("pub mod day"))
+        .collect();
+    if days.contains(&line.as_str()) {
+        return Err(PuzzleError::invalid(format!("`{line}` is already there")));
+    }
+    let Some(&last) = days.last() else {
+        return Err(PuzzleError::invalid("no `pub mod dayN;` lines to add to"));
+    };
+    // Compare names alone: `day1;` sorts after `day13;`, but `day1` first.
+    let name = |l: &str| {
+        l.trim_start_matches("pub mod ")
+            .trim_end_matches(';')
+            .to_string()
+    };
+    let mut out = String::new();
+    let mut added = false;
+    for l in lib.lines() {
+        let here = !added && days.contains(&l) && name(&line) < name(l);
+        if here {
+            out.push_str(&line);
+            out.push('\n');
+            added = true;
+        }
+        out.push_str(l);
+        out.push('\n');
+        if !added && l == last {
+            out.push_str(&line);
+            out.push('\n');
+            added = true;
+        }
+    }
+    Ok(out)
+}
+
+/// `solution.rs` with day `day` imported and added to `SOLUTIONS`.
+pub fn register_solution(source: &str, day: u8) -> Result<String, PuzzleError> {
+    let module = format!("day{day}");
+    let missing = |what: &str| PuzzleError::invalid(format!("cannot find {what} in the registry"));
+
+    let import_start = source
+        .find("use crate::{day")
+        .ok_or_else(|| missing("the import of the days"))?;
+    let import_end = import_start
+        + source[import_start..]
+            .find("};")
+            .ok_or_else(|| missing("the end of the import"))?
+        + 2;
+    let list = &source[import_start + "use crate::{".len()..import_end - 2];
+    let mut modules: Vec<&str> = list.split(',').map(str::trim).collect();
+    modules.retain(|m| !m.is_empty());
+    if modules.contains(&module.as_str()) {
+        return Err(PuzzleError::invalid(format!(
+            "day {day} is already registered"
+        )));
+    }
+    modules.push(&module);
+    modules.sort_unstable();
+    let import = format!("use crate::{{{}}};", modules.join(", "));
+
+    let registry = &source[import_end..];
+    let end = registry
+        .find("\n];")
+        .ok_or_else(|| missing("the end of `SOLUTIONS`"))?;
+    // Entries read `    &dayN::DayN,`; the new one goes before the first
+    // later day, or last.
+    let entries = &registry[..end];
+    let later = entries.lines().find(|l| {
+        l.trim()
+            .strip_prefix("&day")
+            .and_then(|rest| rest.split("::").next())
+            .and_then(|n| n.parse::<u8>().ok())
+            .is_some_and(|n| n > day)
+    });
+    let entry = format!("    &{module}::Day{day},\n");
+    let at = match later {
+        Some(line) => entries.find(line).unwrap_or(end),
+        None => end + 1,
+    };
+
+    let mut out = String::with_capacity(source.len() + 64);
+    out.push_str(&source[..import_start]);
+    out.push_str(&import);
+    out.push_str(&registry[..at]);
+    out.push_str(&entry);
+    out.push_str(&registry[at..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn registers() {
+        let lib =
+            "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod error;\n";
+        assert!(super::register_module(lib, 11)
+            .unwrap()
+            .contains("pub mod day10;\npub mod day11;\npub mod day2;"));
+        assert_eq!(
+            super::register_module(lib, 13).unwrap(),
+            "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day13;\npub mod day2;\n\
+             pub mod error;\n"
+        );
+        assert_eq!(
+            super::register_module(lib, 3).unwrap(),
+            "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\n\
+             pub mod error;\n"
+        );
+        assert!(super::register_module(lib, 2).is_err());
+
+        let solution = "use crate::{day1, day10, day2};\n\n\
+                        pub static SOLUTIONS: &[&dyn Solution] = &[\n    &day1::Day1,\n    \
+                        &day2::Day2,\n    &day10::Day10,\n];\n";
+        assert_eq!(
+            super::register_solution(solution, 3).unwrap(),
+            "use crate::{day1, day10, day2, day3};\n\n\
+             pub static SOLUTIONS: &[&dyn Solution] = &[\n    &day1::Day1,\n    \
+             &day2::Day2,\n    &day3::Day3,\n    &day10::Day10,\n];\n"
+        );
+        assert!(super::register_solution(solution, 11)
+            .unwrap()
+            .ends_with("&day10::Day10,\n    &day11::Day11,\n];\n"));
+        assert!(super::register_solution(solution, 10).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::PuzzleError;
+use crate::vault;
+use crate::{day1, day10, day11, day12, day2, day3, day4, day5, day6, day7, day8, day9};
+
+/// Every implemented day, in calendar order.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+/// Looks up the solution for `day`.
+pub fn find(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// A day's puzzle, parsed once and then solved part by part.
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    /// The parts that have a solver, usually both.
+    fn parts(&self) -> &'static [Part] {
+        &Part::BOTH
+    }
+
+    /// Names of the day-specific parameters `Parsed::solve` understands.
+    fn params(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, PuzzleError>;
+}
+
+/// A day's parsed input, ready to solve any of its parts.
+pub trait Parsed {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, PuzzleError>;
+}
+
+/// Where the puzzle input for `day` is kept, relative to the crate root.
+pub fn default_input(day: u8) -> String {
+    format!("src/day{day}_input.txt")
+}
+
+/// Reads `file_name`, or its sealed copy, and solves one part of it.
+pub fn solve_file(
+    solution: &dyn Solution,
+    part: Part,
+    file_name: &str,
+    params: &Params,
+) -> Result<Answer, PuzzleError> {
+    vault::with_input(file_name, |input| {
+        solution.parse(input)?.solve(part, params)
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to one part, wide enough to hold every day's result type.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Answer(pub i128);
+
+macro_rules! answer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer(value as i128)
+            }
+        })*
+    };
+}
+
+answer_from!(u32, u64, usize, i64, isize);
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Day-specific parameters, such as day 11's `multiplicator`, by name.
+#[derive(Debug, Default, Clone)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    /// Parses the parameter `name`, falling back to `default` when it is unset.
+    pub fn get_or<T>(&self, name: &str, default: T) -> Result<T, PuzzleError>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.0.get(name) {
+            Some(value) => value.parse().map_err(|e: T::Err| PuzzleError::Param {
+                name: name.to_string(),
+                value: value.clone(),
+                reason: e.to_string(),
+            }),
+            None => Ok(default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Params, Part, SOLUTIONS};
+
+    #[test]
+    fn registry_in_calendar_order() {
+        let days: Vec<_> = SOLUTIONS.iter().map(|s| s.day()).collect();
+        assert_eq!(days[..12], (1..=12).collect::<Vec<_>>());
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{days:?}");
+        assert_eq!(super::find(12).unwrap().parts(), &Part::BOTH);
+    }
+
+    #[test]
+    fn params() {
+        let mut params = Params::default();
+        assert_eq!(params.get_or("multiplicator", 2).unwrap(), 2);
+        params.set("multiplicator", "10");
+        assert_eq!(params.get_or("multiplicator", 2).unwrap(), 10);
+        params.set("multiplicator", "ten");
+        assert_eq!(
+            params.get_or("multiplicator", 2).unwrap_err().to_string(),
+            "invalid value `ten` for `multiplicator`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Submitting answers to the puzzle site, and keeping a history of every
+//! attempt so a guess already known to be wrong is never sent again.
+//!
+//! The history lives in `submissions.txt` at the crate root. Each line reads
+//! `day part answer verdict time`, the time in seconds since the Unix epoch;
+//! `#` starts a comment. A "too high" or "too low" verdict also rules out
+//! every answer beyond it, and a rate limit, recorded as `rate-limited:S`
+//! for a wait of `S` seconds, holds off every submission until it runs out.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use nom::error::ErrorKind;
+
+use crate::error::PuzzleError;
+use crate::http;
+use crate::solution::{Answer, Part};
+
+/// Where the submission history is kept, relative to the crate root.
+pub const HISTORY_FILE: &str = "submissions.txt";
+
+/// What the site made of a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted too soon after the last attempt; try again after the wait.
+    RateLimited(Duration),
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// A response none of the above matched.
+    Unrecognised,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the site answers a submission with.
+    pub fn from_page(page: &str) -> Verdict {
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::RateLimited(wait(page).unwrap_or_default())
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unrecognised
+        }
+    }
+
+    fn name(self) -> String {
+        match self {
+            Verdict::Correct => "correct".to_string(),
+            Verdict::TooHigh => "too-high".to_string(),
+            Verdict::TooLow => "too-low".to_string(),
+            Verdict::Wrong => "wrong".to_string(),
+            Verdict::RateLimited(wait) => format!("rate-limited:{}", wait.as_secs()),
+            Verdict::AlreadySolved => "already-solved".to_string(),
+            Verdict::Unrecognised => "unrecognised".to_string(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        if let Some(seconds) = name.strip_prefix("rate-limited:") {
+            return seconds
+                .parse()
+                .ok()
+                .map(|s| Verdict::RateLimited(Duration::from_secs(s)));
+        }
+        Some(match name {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "already-solved" => Verdict::AlreadySolved,
+            "unrecognised" => Verdict::Unrecognised,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unrecognised => write!(f, "unrecognised response"),
+        }
+    }
+}
+
+/// The wait in "You have 1m 5s left to wait."
+fn wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end]
+        .split_whitespace()
+        .try_fold(0, |total, amount| {
+            let unit = match amount.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            let count: u64 = amount[..amount.len() - 1].parse().ok()?;
+            Some(total + count * unit)
+        })
+        .map(Duration::from_secs)
+}
+
+/// One recorded submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    /// When the answer was submitted.
+    pub at: SystemTime,
+}
+
+impl Attempt {
+    /// How much longer the site asked to wait before submitting again, as of
+    /// `now`.
+    pub fn wait_left(&self, now: SystemTime) -> Option<Duration> {
+        let Verdict::RateLimited(wait) = self.verdict else {
+            return None;
+        };
+        let left = (self.at + wait).duration_since(now).ok()?;
+        (!left.is_zero()).then_some(left)
+    }
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Attempt {
+            day,
+            part,
+            answer,
+            verdict,
+            at,
+        } = self;
+        let seconds = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        write!(f, "{day} {part} {answer} {} {seconds}", verdict.name())
+    }
+}
+
+pub fn parse_history(text: &str) -> Result<Vec<Attempt>, PuzzleError> {
+    let mut attempts = Vec::new();
+    for line in text.lines() {
+        let content = line.split('#').next().unwrap_or_default();
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let [day, part, answer, verdict, at] = fields[..] else {
+            return Err(PuzzleError::parse(text, line, ErrorKind::Count));
+        };
+        let day = day
+            .parse()
+            .map_err(|_| PuzzleError::parse(text, day, ErrorKind::Digit))?;
+        let part = part
+            .parse()
+            .ok()
+            .and_then(Part::from_number)
+            .ok_or_else(|| PuzzleError::parse(text, part, ErrorKind::OneOf))?;
+        let answer = answer
+            .parse()
+            .map(Answer)
+            .map_err(|_| PuzzleError::parse(text, answer, ErrorKind::Digit))?;
+        let verdict = Verdict::from_name(verdict)
+            .ok_or_else(|| PuzzleError::parse(text, verdict, ErrorKind::Tag))?;
+        let seconds = at
+            .parse()
+            .map_err(|_| PuzzleError::parse(text, at, ErrorKind::Digit))?;
+        let at = UNIX_EPOCH + Duration::from_secs(seconds);
+        attempts.push(Attempt {
+            day,
+            part,
+            answer,
+            verdict,
+            at,
+        });
+    }
+    Ok(attempts)
+}
+
+/// Appends `attempt` to the history file `file`, creating it if need be.
+pub fn record(file: &str, attempt: &Attempt) -> Result<(), PuzzleError> {
+    let io_error = |error| PuzzleError::Io {
+        file: file.to_string(),
+        error,
+    };
+    let mut history = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)
+        .map_err(io_error)?;
+    writeln!(history, "{attempt}").map_err(io_error)
+}
+
+/// Why an answer is not worth submitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect(Answer),
+    AlreadyWrong,
+    /// At or above an answer already found too high.
+    TooHigh(Answer),
+    /// At or below an answer already found too low.
+    TooLow(Answer),
+    /// The site asked to wait this much longer before the next answer.
+    RateLimited(Duration),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => write!(f, "already solved with {answer}"),
+            Refusal::AlreadyWrong => write!(f, "already submitted and found wrong"),
+            Refusal::TooHigh(bound) => write!(f, "{bound} was already too high"),
+            Refusal::TooLow(bound) => write!(f, "{bound} was already too low"),
+            Refusal::RateLimited(wait) => {
+                write!(f, "rate limited for another {}s", wait.as_secs_f64().ceil())
+            }
+        }
+    }
+}
+
+/// Checks `answer` to `part` of `day` against what `history` has learned,
+/// and that no rate limit is still running at `now`. The site limits every
+/// submission, whatever its day.
+pub fn vet(
+    history: &[Attempt],
+    day: u8,
+    part: Part,
+    answer: Answer,
+    now: SystemTime,
+) -> Result<(), Refusal> {
+    let attempts = || {
+        history
+            .iter()
+            .filter(move |a| (a.day, a.part) == (day, part))
+    };
+    if let Some(correct) = attempts().find(|a| a.verdict == Verdict::Correct) {
+        return Err(Refusal::AlreadyCorrect(correct.answer));
+    }
+    let wrong = [Verdict::Wrong, Verdict::TooHigh, Verdict::TooLow];
+    if attempts().any(|a| a.answer == answer && wrong.contains(&a.verdict)) {
+        return Err(Refusal::AlreadyWrong);
+    }
+    let lowest_high = attempts()
+        .filter(|a| a.verdict == Verdict::TooHigh)
+        .map(|a| a.answer)
+        .min();
+    if let Some(bound) = lowest_high.filter(|&bound| answer >= bound) {
+        return Err(Refusal::TooHigh(bound));
+    }
+    let highest_low = attempts()
+        .filter(|a| a.verdict == Verdict::TooLow)
+        .map(|a| a.answer)
+        .max();
+    if let Some(bound) = highest_low.filter(|&bound| answer <= bound) {
+        return Err(Refusal::TooLow(bound));
+    }
+    if let Some(wait) = history.iter().filter_map(|a| a.wait_left(now)).max() {
+        return Err(Refusal::RateLimited(wait));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Submitter {
+    /// Posts `answer` to `part` of `day` and reads the verdict.
+    pub fn submit(&self, day: u8, part: Part, answer: Answer) -> Result<Verdict, PuzzleError> {
+        let url = format!("{}/day/{day}/answer", self.base_url.trim_end_matches('/'));
+        let cookie = http::session_cookie(&self.session).map_err(|error| PuzzleError::Io {
+            file: url.clone(),
+            error,
+        })?;
+        let response = http::request(
+            "POST",
+            &url,
+            &[
+                ("Cookie", &cookie),
+                ("Content-Type", "application/x-www-form-urlencoded"),
+            ],
+            Some(&format!("level={part}&answer={answer}")),
+        )?;
+        if !response.is_success() {
+            return Err(PuzzleError::Io {
+                file: url,
+                error: io::Error::other(format!(
+                    "the server answered {} {}",
+                    response.status, response.reason
+                )),
+            });
+        }
+        Ok(Verdict::from_page(&response.body))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{Attempt, Refusal, Submitter, Verdict};
+    use crate::http::stand_in;
+    use crate::solution::{Answer, Part};
+
+    #[test]
+    fn verdicts() {
+        let page = |text: &str| Verdict::from_page(&format!("<main><article><p>{text}</p>"));
+        assert_eq!(
+            page("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            page("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            page("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            page("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            page("You gave an answer too recently. You have 1m 5s left to wait."),
+            Verdict::RateLimited(Duration::from_secs(65))
+        );
+        assert_eq!(
+            page("You don't seem to be solving the right level."),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(page("Puzzle inputs differ by user."), Verdict::Unrecognised);
+    }
+
+    #[test]
+    fn history() {
+        let text = "# day part answer verdict time\n1 1 500 too-high 1699999000\n\
+                    1 1 100 too-low 1699999100\n1 1 300 wrong 1699999200\n";
+        let history = super::parse_history(text).unwrap();
+        let attempt = Attempt {
+            day: 1,
+            part: Part::One,
+            answer: Answer(500),
+            verdict: Verdict::TooHigh,
+            at: UNIX_EPOCH + Duration::from_secs(1_699_999_000),
+        };
+        assert_eq!(history[0], attempt);
+        assert_eq!(attempt.to_string(), "1 1 500 too-high 1699999000");
+        assert_eq!(
+            super::parse_history("1 1 5 maybe 0")
+                .unwrap_err()
+                .to_string(),
+            "<input>:1:7: unexpected input (Tag)"
+        );
+        assert_eq!(
+            super::parse_history("1 1 5 wrong").unwrap_err().to_string(),
+            "<input>:1:1: unexpected input (Count)"
+        );
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let vet = |part, answer| super::vet(&history, 1, part, Answer(answer), now);
+        assert_eq!(vet(Part::One, 300), Err(Refusal::AlreadyWrong));
+        assert_eq!(vet(Part::One, 600), Err(Refusal::TooHigh(Answer(500))));
+        assert_eq!(vet(Part::One, 50), Err(Refusal::TooLow(Answer(100))));
+        assert_eq!(vet(Part::One, 250), Ok(()));
+        assert_eq!(vet(Part::Two, 600), Ok(()));
+
+        let mut solved = history.clone();
+        solved.push(Attempt {
+            answer: Answer(250),
+            verdict: Verdict::Correct,
+            ..attempt
+        });
+        assert_eq!(
+            super::vet(&solved, 1, Part::One, Answer(251), now),
+            Err(Refusal::AlreadyCorrect(Answer(250)))
+        );
+    }
+
+    #[test]
+    fn rate_limits() {
+        let text = "2 1 7 wrong 1699999990\n1 1 300 rate-limited:65 1700000000\n";
+        let history = super::parse_history(text).unwrap();
+        assert_eq!(
+            history[1].verdict,
+            Verdict::RateLimited(Duration::from_secs(65))
+        );
+        assert_eq!(history[1].to_string(), "1 1 300 rate-limited:65 1700000000");
+        assert_eq!(
+            super::parse_history("1 1 300 rate-limited 1700000000")
+                .unwrap_err()
+                .to_string(),
+            "<input>:1:9: unexpected input (Tag)"
+        );
+
+        // The limit holds for every day, until the wait has run out.
+        let at = |seconds: u64| UNIX_EPOCH + Duration::from_secs(1_700_000_000 + seconds);
+        let vet = |now| super::vet(&history, 5, Part::Two, Answer(1), now);
+        assert_eq!(
+            vet(at(5)),
+            Err(Refusal::RateLimited(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            vet(at(5)).unwrap_err().to_string(),
+            "rate limited for another 60s"
+        );
+        assert_eq!(vet(at(65)), Ok(()));
+        assert_eq!(vet(at(1000)), Ok(()));
+    }
+
+    #[test]
+    fn submits() {
+        let body = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+        let (base_url, server) = stand_in(vec![format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )]);
+        let submitter = Submitter {
+            base_url,
+            session: "cafe".into(),
+        };
+        assert_eq!(
+            submitter.submit(7, Part::Two, Answer(42)).unwrap(),
+            Verdict::TooLow
+        );
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=cafe\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=42"));
+    }
+}
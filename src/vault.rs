@@ -0,0 +1,188 @@
+//! Puzzle inputs sealed with a key, so they can be committed without
+//! publishing them.
+//!
+//! `src/dayN_input.txt` is sealed into `src/dayN_input.txt.vault`. Whoever
+//! has the key in `AOC_VAULT_KEY` reads the sealed input as if it were the
+//! plain one; without the key, the input counts as missing.
+//!
+//! A vault file is text: a `my-project vault 1` line, then the hex of the
+//! salt, the nonce and the ciphertext, one per line, the ciphertext wrapped
+//! every 32 bytes. The key for each file is stretched from the passphrase
+//! and the file's random salt with Argon2id, and the input sealed under it
+//! with ChaCha20-Poly1305 and a random nonce, so sealing the same input
+//! twice gives two different files.
+
+use std::env;
+use std::io;
+use std::path::Path;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use nom::error::ErrorKind;
+
+use crate::bytes::{hex, random, unhex, unhex_exact};
+use crate::error::{self, PuzzleError};
+
+/// The environment variable holding the key.
+pub const KEY_VAR: &str = "AOC_VAULT_KEY";
+
+const MAGIC: &str = "my-project vault 1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// A passphrase, from which each vault file's own key is stretched.
+pub struct Key {
+    passphrase: String,
+}
+
+impl Key {
+    pub fn from_passphrase(passphrase: &str) -> Key {
+        Key {
+            passphrase: passphrase.to_string(),
+        }
+    }
+
+    /// The key in `AOC_VAULT_KEY`, if it is set and not blank.
+    pub fn from_env() -> Option<Key> {
+        let passphrase = env::var(KEY_VAR).ok()?;
+        let passphrase = passphrase.trim();
+        (!passphrase.is_empty()).then(|| Key::from_passphrase(passphrase))
+    }
+
+    /// The cipher for the file with `salt`.
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305, PuzzleError> {
+        let mut key = [0; 32];
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| PuzzleError::invalid(format!("cannot stretch the key: {e}")))?;
+        Ok(ChaCha20Poly1305::new(&key.into()))
+    }
+}
+
+/// Seals `input` under `key`.
+pub fn seal(key: &Key, input: &str) -> Result<String, PuzzleError> {
+    let salt: [u8; SALT_LEN] = random()?;
+    let nonce: [u8; NONCE_LEN] = random()?;
+    let payload = Payload {
+        msg: input.as_bytes(),
+        aad: MAGIC.as_bytes(),
+    };
+    let ciphertext = key
+        .cipher(&salt)?
+        .encrypt(&nonce.into(), payload)
+        .map_err(|_| PuzzleError::invalid("the input is too long to seal"))?;
+    let mut sealed = format!("{MAGIC}\n{}\n{}\n", hex(&salt), hex(&nonce));
+    for line in ciphertext.chunks(32) {
+        sealed.push_str(&hex(line));
+        sealed.push('\n');
+    }
+    Ok(sealed)
+}
+
+/// Opens what [`seal`] produced, failing if it was sealed under another key
+/// or has been tampered with.
+pub fn open(key: &Key, sealed: &str) -> Result<String, PuzzleError> {
+    let mut lines = sealed.lines();
+    let magic = lines.next().unwrap_or_default();
+    if magic != MAGIC {
+        return Err(PuzzleError::parse(sealed, magic, ErrorKind::Tag));
+    }
+    let salt: [u8; SALT_LEN] = unhex_exact(sealed, lines.next().unwrap_or_default())?;
+    let nonce: [u8; NONCE_LEN] = unhex_exact(sealed, lines.next().unwrap_or_default())?;
+    let mut ciphertext = Vec::new();
+    for line in lines {
+        ciphertext.extend(unhex(sealed, line)?);
+    }
+    let payload = Payload {
+        msg: &ciphertext,
+        aad: MAGIC.as_bytes(),
+    };
+    let data = key
+        .cipher(&salt)?
+        .decrypt(&nonce.into(), payload)
+        .map_err(|_| {
+            PuzzleError::invalid("the vault does not open with this key, or it was modified")
+        })?;
+    String::from_utf8(data).map_err(|_| PuzzleError::invalid("the sealed input is not UTF-8"))
+}
+
+/// Where the sealed copy of the input at `input` is kept.
+pub fn sealed_path(input: &str) -> String {
+    format!("{input}.vault")
+}
+
+/// Whether the input at `input` exists, in the clear or sealed.
+pub fn exists(input: &str) -> bool {
+    Path::new(input).exists() || Path::new(&sealed_path(input)).exists()
+}
+
+/// Whether the input at `input` can be read: it exists in the clear, or
+/// sealed with the key at hand.
+pub fn available(input: &str) -> bool {
+    Path::new(input).exists()
+        || Path::new(&sealed_path(input)).exists() && Key::from_env().is_some()
+}
+
+/// Like [`error::with_input`], but opens the sealed copy of `file_name` when
+/// there is no plain one.
+pub fn with_input<T>(
+    file_name: &str,
+    solve: impl FnOnce(&str) -> Result<T, PuzzleError>,
+) -> Result<T, PuzzleError> {
+    let sealed = sealed_path(file_name);
+    if Path::new(file_name).exists() || !Path::new(&sealed).exists() {
+        return error::with_input(file_name, solve);
+    }
+    let key = Key::from_env().ok_or_else(|| PuzzleError::Io {
+        file: sealed.clone(),
+        error: io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("the input is sealed; set {KEY_VAR}"),
+        ),
+    })?;
+    let input = error::with_input(&sealed, |text| open(&key, text))?;
+    solve(&input).map_err(|e| e.in_file(file_name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Key;
+
+    #[test]
+    fn seal_and_open() {
+        let key = Key::from_passphrase("correct horse battery staple");
+        let input = "467..114..\n...*......\n".repeat(5);
+        let sealed = super::seal(&key, &input).unwrap();
+        assert!(!sealed.contains(&crate::bytes::hex(&input.as_bytes()[..8])));
+        assert_eq!(super::open(&key, &sealed).unwrap(), input);
+        // Fresh salt and nonce every time.
+        let again = super::seal(&key, &input).unwrap();
+        assert_ne!(sealed.lines().nth(1), again.lines().nth(1));
+        assert_ne!(sealed.lines().nth(2), again.lines().nth(2));
+        assert_eq!(super::open(&key, &again).unwrap(), input);
+
+        let other = Key::from_passphrase("Tr0ub4dor&3");
+        assert!(super::open(&other, &sealed).is_err());
+        let mut lines: Vec<String> = sealed.lines().map(String::from).collect();
+        let flipped = if lines[3].starts_with("00") {
+            "01"
+        } else {
+            "00"
+        };
+        lines[3].replace_range(..2, flipped);
+        assert!(super::open(&key, &lines.join("\n")).is_err());
+        assert_eq!(
+            super::open(&key, "my-project vault 1\nxyz\n")
+                .unwrap_err()
+                .to_string(),
+            "<input>:2:1: unexpected input (Hexadecimal Digit)"
+        );
+        assert_eq!(
+            super::open(&key, "my-project vault 0\n00\n")
+                .unwrap_err()
+                .to_string(),
+            "<input>:1:1: unexpected input (Tag)"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Running a solver on a worker thread of its own, so that one that hangs or
+//! panics costs its day an error rather than the whole run.
+//!
+//! A worker that runs out of time cannot be stopped: it is left to spin on
+//! in the background until the process exits. A stack overflow still aborts
+//! the process, as it does on any thread, so deep recursion is handled with a
+//! bigger stack rather than caught.
+
+use std::any::Any;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::PuzzleError;
+use crate::solution::{Answer, Params, Part, Solution};
+use crate::vault;
+
+/// Room for solvers that recurse deeply, well beyond the main thread's stack.
+pub const DEFAULT_STACK_SIZE: usize = 64 << 20;
+
+/// Long enough for every day so far, by a wide margin.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What a worker may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub stack_size: usize,
+    /// How long to wait for the worker; `None` waits for as long as it takes.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            stack_size: DEFAULT_STACK_SIZE,
+            timeout: Some(DEFAULT_TIMEOUT),
+        }
+    }
+}
+
+/// Runs `job` on a worker thread called `name` within `limits`, turning a
+/// panic or a timeout into an error. The name shows in the panic message
+/// Rust prints; the error leaves it to the caller.
+pub fn run<T: Send + 'static>(
+    name: &str,
+    limits: &Limits,
+    job: impl FnOnce() -> Result<T, PuzzleError> + Send + 'static,
+) -> Result<T, PuzzleError> {
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .name(name.to_string())
+        .stack_size(limits.stack_size)
+        .spawn(move || {
+            // The receiver is gone if the wait timed out; nobody is listening.
+            let _ = sender.send(job());
+        })
+        .map_err(|e| PuzzleError::Aborted {
+            reason: format!("cannot start a worker: {e}"),
+        })?;
+    let received = match limits.timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(result) => {
+            let _ = worker.join();
+            result
+        }
+        Err(RecvTimeoutError::Timeout) => Err(PuzzleError::Aborted {
+            reason: format!("gave up after {:?}", limits.timeout.unwrap_or_default()),
+        }),
+        // The sender was dropped without sending: the job panicked.
+        Err(RecvTimeoutError::Disconnected) => {
+            let message = worker.join().err().map_or_else(
+                || "the worker stopped without an answer".to_string(),
+                panic_message,
+            );
+            Err(PuzzleError::Aborted {
+                reason: format!("panicked: {message}"),
+            })
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| "no message".to_string(), |s| s.to_string()),
+    }
+}
+
+/// Parses `input` and solves one part of it on a worker.
+pub fn solve(
+    solution: &'static dyn Solution,
+    part: Part,
+    input: String,
+    params: Params,
+    limits: &Limits,
+) -> Result<Answer, PuzzleError> {
+    let name = format!("day {} part {part}", solution.day());
+    run(&name, limits, move || {
+        solution.parse(&input)?.solve(part, &params)
+    })
+}
+
+/// Like [`solution::solve_file`](crate::solution::solve_file), but on a
+/// worker.
+pub fn solve_file(
+    solution: &'static dyn Solution,
+    part: Part,
+    file_name: &str,
+    params: &Params,
+    limits: &Limits,
+) -> Result<Answer, PuzzleError> {
+    vault::with_input(file_name, |input| {
+        solve(solution, part, input.to_string(), params.clone(), limits)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::Limits;
+    use crate::error::PuzzleError;
+
+    #[test]
+    fn contains_failures() {
+        let limits = Limits {
+            stack_size: 1 << 20,
+            timeout: Some(Duration::from_millis(100)),
+        };
+        assert_eq!(super::run("sum", &limits, || Ok(1 + 2)).unwrap(), 3);
+        assert!(matches!(
+            super::run("parse", &limits, || Err::<(), _>(PuzzleError::invalid(
+                "no"
+            ))),
+            Err(PuzzleError::Invalid { .. })
+        ));
+        assert_eq!(
+            super::run("panic", &limits, || -> Result<(), _> { panic!("at {}", 7) })
+                .unwrap_err()
+                .to_string(),
+            "panicked: at 7"
+        );
+        assert_eq!(
+            super::run("hang", &limits, || -> Result<(), _> {
+                loop {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            })
+            .unwrap_err()
+            .to_string(),
+            "gave up after 100ms"
+        );
+    }
+}